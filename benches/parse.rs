@@ -31,6 +31,26 @@ fn parse_json(data: &[u8]) {
     }
 }
 
+fn parse_json_tape(data: &[u8]) {
+    let a = json_deserializer::parse_tape(data).unwrap();
+    assert!(a.root().iter_array().is_some());
+}
+
+fn skip_json(data: &[u8]) {
+    let mut values = data;
+    json_deserializer::skip_value(&mut values).unwrap();
+    assert!(values.iter().all(u8::is_ascii_whitespace));
+}
+
+#[cfg(feature = "serde")]
+fn parse_json_typed(data: &[u8]) {
+    let a: serde_mapping::Value = json_deserializer::from_slice(data).unwrap();
+    if let serde_mapping::Value::Array(_) = a {
+    } else {
+        panic!()
+    }
+}
+
 fn read(file: &str, log2_size: usize) -> Vec<u8> {
     let mut f = std::fs::File::open(format!("data/{}_{}.json", file, log2_size)).unwrap();
     let mut data = vec![];
@@ -48,6 +68,7 @@ fn add_benchmark(c: &mut Criterion) {
             "bool",
             "object_string",
             "object_bool",
+            "object_indented",
         ] {
             let bytes = read(type_, log2_size);
 
@@ -56,6 +77,16 @@ fn add_benchmark(c: &mut Criterion) {
                 |b| b.iter(|| parse_json(&bytes)),
             );
 
+            c.bench_function(
+                &format!("{} json_deserializer_tape 2^{}", type_, log2_size),
+                |b| b.iter(|| parse_json_tape(&bytes)),
+            );
+
+            c.bench_function(
+                &format!("{} json_deserializer_skip 2^{}", type_, log2_size),
+                |b| b.iter(|| skip_json(&bytes)),
+            );
+
             c.bench_function(&format!("{} serde_json 2^{}", type_, log2_size), |b| {
                 b.iter(|| parse_serde_json(&bytes))
             });
@@ -65,6 +96,12 @@ fn add_benchmark(c: &mut Criterion) {
                 |b| b.iter(|| parse_serde_json_typed(&bytes)),
             );
 
+            #[cfg(feature = "serde")]
+            c.bench_function(
+                &format!("{} json_deserializer_custom 2^{}", type_, log2_size),
+                |b| b.iter(|| parse_json_typed(&bytes)),
+            );
+
             let bytes = bytes.clone();
             c.bench_function(&format!("{} simd_json 2^{}", type_, log2_size), move |b| {
                 b.iter_batched(
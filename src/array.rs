@@ -1,17 +1,23 @@
 use alloc::vec::Vec;
 
 use crate::{
-    parser::{current_token, parse_value, skip_unused},
-    Value,
+    parser::{check_depth, current_token, parse_value_in, Storage},
+    relaxed::{is_trailing_comma, skip_ignored},
+    ParseOptions, Value,
 };
 
 use super::error::*;
 
-pub fn parse_array<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Vec<Value<'a>>, Error> {
+pub fn parse_array<'a, 'b, S: Storage<'a, 'b>>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<Vec<Value<'b>>, ErrorKind> {
+    check_depth(options, depth)?;
     *values = &values[1..];
     let mut items = vec![];
     loop {
-        skip_unused(values);
+        skip_ignored(values, options)?;
         let token = current_token(values)?;
         if token == b']' {
             *values = &values[1..];
@@ -19,13 +25,16 @@ pub fn parse_array<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Vec<Value<'a>>, E
         };
         if !items.is_empty() {
             if token != b',' {
-                return Err(Error::MissingComma(token));
+                return Err(ErrorKind::MissingComma(token));
             } else {
                 *values = &values[1..]; // consume ","
             }
+            if is_trailing_comma(values, b']', options)? {
+                break;
+            }
         }
 
-        items.push(parse_value(values)?);
+        items.push(parse_value_in::<S>(values, options, depth + 1)?);
     }
     Ok(items)
 }
@@ -0,0 +1,811 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::mem::take;
+
+use arrow_array::builder::{
+    ArrayBuilder, BooleanBuilder, LargeStringBuilder, PrimitiveBuilder, StringBuilder,
+};
+use arrow_array::types::{
+    Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type,
+    UInt64Type, UInt8Type,
+};
+use arrow_array::{
+    ArrayRef, ArrowPrimitiveType, LargeListArray, ListArray, NullArray, RecordBatch,
+    RecordBatchOptions, StructArray,
+};
+use arrow_buffer::{NullBufferBuilder, OffsetBuffer, ScalarBuffer};
+use arrow_schema::{ArrowError, DataType, Field, FieldRef, Fields, Schema, SchemaRef};
+
+use super::error::*;
+use super::infer::{InferredField, InferredType};
+use super::ndjson::for_each_line;
+use super::options::{DuplicateKeys, ParseOptions};
+use super::parser::Number;
+use super::visitor::{visit_with_options, Visitor};
+
+/// Options to parse JSON into Arrow [`RecordBatch`]es, built from [`ArrowOptions::default`]
+/// and its `with_*` methods
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ArrowOptions {
+    /// Options used to parse the JSON
+    pub parse: ParseOptions,
+    /// The maximum number of rows of each [`RecordBatch`]
+    pub batch_size: usize,
+}
+
+impl Default for ArrowOptions {
+    fn default() -> Self {
+        Self {
+            parse: ParseOptions::default(),
+            batch_size: 1024,
+        }
+    }
+}
+
+impl ArrowOptions {
+    /// Returns these options with [`ArrowOptions::parse`] set to `parse`
+    pub fn with_parse(mut self, parse: ParseOptions) -> Self {
+        self.parse = parse;
+        self
+    }
+
+    /// Returns these options with [`ArrowOptions::batch_size`] set to `batch_size`
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+}
+
+/// Parses a JSON array of objects (records) into [`RecordBatch`]es of `schema`, without
+/// building [`Value`](crate::Value)s.
+///
+/// Each object is a row whose keys are the names of the fields of `schema`: unknown keys are
+/// ignored and missing fields are null. A repeated key errors with
+/// [`ErrorKind::DuplicateKey`] since its value can't be replaced once appended, unless
+/// [`ParseOptions::duplicate_keys`] is [`DuplicateKeys::FirstWins`], which skips later values.
+/// Fields of type `Null`, `Boolean`, `Int*`, `UInt*`,
+/// `Float32`, `Float64`, `Utf8`, `LargeUtf8`, `List`, `LargeList` and `Struct` are supported.
+/// # Errors
+/// If `json` is not valid JSON, `schema` has an unsupported field or a value does not match
+/// its field, e.g. a string for an `Int64` field or a null for a non-nullable field.
+pub fn parse_record_batches(json: &[u8], schema: SchemaRef) -> Result<Vec<RecordBatch>, Error> {
+    parse_record_batches_with_options(json, schema, &ArrowOptions::default())
+}
+
+/// Parses a JSON array of objects into [`RecordBatch`]es of `schema` according to `options`.
+/// See [`parse_record_batches`].
+/// # Errors
+/// If `json` is not valid JSON, it does not fulfill `options` or it does not match `schema`.
+pub fn parse_record_batches_with_options(
+    json: &[u8],
+    schema: SchemaRef,
+    options: &ArrowOptions,
+) -> Result<Vec<RecordBatch>, Error> {
+    let mut decoder = Decoder::try_new(schema, options, true).map_err(|e| Error::new(e, 0))?;
+    visit_with_options(json, &options.parse, &mut decoder)?;
+    decoder.finish().map_err(|e| Error::new(e, json.len()))
+}
+
+/// Parses newline-delimited JSON whose lines are objects (records) into [`RecordBatch`]es of
+/// `schema`, skipping blank lines. See [`parse_record_batches`].
+/// # Errors
+/// If a line is not valid JSON or it does not match `schema`. The error contains the byte
+/// offset in the whole input.
+pub fn parse_ndjson_record_batches(
+    json: &[u8],
+    schema: SchemaRef,
+) -> Result<Vec<RecordBatch>, Error> {
+    parse_ndjson_record_batches_with_options(json, schema, &ArrowOptions::default())
+}
+
+/// Parses newline-delimited JSON into [`RecordBatch`]es of `schema` according to `options`.
+/// See [`parse_ndjson_record_batches`].
+/// # Errors
+/// If a line is not valid JSON, it does not fulfill `options` or it does not match `schema`.
+pub fn parse_ndjson_record_batches_with_options(
+    json: &[u8],
+    schema: SchemaRef,
+    options: &ArrowOptions,
+) -> Result<Vec<RecordBatch>, Error> {
+    let mut decoder = Decoder::try_new(schema, options, false).map_err(|e| Error::new(e, 0))?;
+    for_each_line(json, |line| {
+        visit_with_options(line, &options.parse, &mut decoder)
+    })?;
+    decoder.finish().map_err(|e| Error::new(e, json.len()))
+}
+
+impl InferredType {
+    /// Returns the Arrow [`Schema`] of records of this type, e.g. to parse them with
+    /// [`parse_ndjson_record_batches`]. Integers are `Int64`, floats are `Float64` and strings
+    /// are `Utf8`.
+    /// # Errors
+    /// If this is not a struct or it contains a union, which is not supported.
+    pub fn to_arrow_schema(&self) -> Result<Schema, ArrowError> {
+        match self.to_arrow()? {
+            DataType::Struct(fields) => Ok(Schema::new(fields)),
+            other => Err(ArrowError::SchemaError(format!(
+                "records must be structs, found {other}"
+            ))),
+        }
+    }
+
+    fn to_arrow(&self) -> Result<DataType, ArrowError> {
+        Ok(match self {
+            Self::Null => DataType::Null,
+            Self::Boolean => DataType::Boolean,
+            Self::Integer => DataType::Int64,
+            Self::Float => DataType::Float64,
+            Self::String => DataType::Utf8,
+            Self::List(item) => DataType::List(Arc::new(item.to_arrow()?)),
+            Self::Struct(fields) => DataType::Struct(
+                fields
+                    .iter()
+                    .map(InferredField::to_arrow)
+                    .collect::<Result<Fields, _>>()?,
+            ),
+            Self::Union(_) => {
+                return Err(ArrowError::SchemaError(
+                    "unions are not supported".to_string(),
+                ))
+            }
+        })
+    }
+}
+
+impl InferredField {
+    fn to_arrow(&self) -> Result<Field, ArrowError> {
+        // `Null` fields are nullable in Arrow
+        let nullable = self.nullable || self.data_type == InferredType::Null;
+        Ok(Field::new(&self.name, self.data_type.to_arrow()?, nullable))
+    }
+}
+
+fn arrow_error(error: ArrowError) -> ErrorKind {
+    ErrorKind::Custom(error.to_string())
+}
+
+/// Arrow types whose values are decoded from numbers
+trait FromNumber: ArrowPrimitiveType {
+    fn from_number(number: &Number) -> Result<Self::Native, ErrorKind>;
+}
+
+macro_rules! from_number {
+    ($type:ty, $method:ident) => {
+        impl FromNumber for $type {
+            fn from_number(number: &Number) -> Result<Self::Native, ErrorKind> {
+                number.$method()
+            }
+        }
+    };
+    ($type:ty, $method:ident, narrow) => {
+        impl FromNumber for $type {
+            fn from_number(number: &Number) -> Result<Self::Native, ErrorKind> {
+                number
+                    .$method()?
+                    .try_into()
+                    .map_err(|_| ErrorKind::NumberOverflow)
+            }
+        }
+    };
+}
+
+from_number!(Int8Type, to_i64, narrow);
+from_number!(Int16Type, to_i64, narrow);
+from_number!(Int32Type, to_i64, narrow);
+from_number!(Int64Type, to_i64);
+from_number!(UInt8Type, to_u64, narrow);
+from_number!(UInt16Type, to_u64, narrow);
+from_number!(UInt32Type, to_u64, narrow);
+from_number!(UInt64Type, to_u64);
+from_number!(Float32Type, to_f32);
+from_number!(Float64Type, to_f64);
+
+/// A builder of an array of numbers, erasing their Arrow type
+trait NumberBuilder {
+    fn append(&mut self, number: &Number) -> Result<(), ErrorKind>;
+
+    fn append_null(&mut self);
+
+    fn len(&self) -> usize;
+
+    fn finish(&mut self) -> ArrayRef;
+}
+
+impl<T: FromNumber> NumberBuilder for PrimitiveBuilder<T> {
+    fn append(&mut self, number: &Number) -> Result<(), ErrorKind> {
+        self.append_value(T::from_number(number)?);
+        Ok(())
+    }
+
+    fn append_null(&mut self) {
+        PrimitiveBuilder::append_null(self)
+    }
+
+    fn len(&self) -> usize {
+        self.values_slice().len()
+    }
+
+    fn finish(&mut self) -> ArrayRef {
+        Arc::new(PrimitiveBuilder::finish(self))
+    }
+}
+
+fn number<T: FromNumber>() -> Builder {
+    Builder::Number(Box::new(PrimitiveBuilder::<T>::new()))
+}
+
+/// Decodes the values of a field into an Arrow array
+struct Column {
+    field: FieldRef,
+    builder: Builder,
+}
+
+enum Builder {
+    Null(usize),
+    Boolean(BooleanBuilder),
+    Number(Box<dyn NumberBuilder>),
+    Utf8(StringBuilder),
+    LargeUtf8(LargeStringBuilder),
+    List {
+        offsets: Vec<usize>,
+        nulls: NullBufferBuilder,
+        item: Box<Column>,
+    },
+    Struct {
+        columns: Vec<Column>,
+        // the index of each field by name
+        indices: BTreeMap<String, usize>,
+        nulls: NullBufferBuilder,
+        len: usize,
+    },
+}
+
+impl Column {
+    fn try_new(field: FieldRef) -> Result<Self, ErrorKind> {
+        let builder = match field.data_type() {
+            DataType::Null => Builder::Null(0),
+            DataType::Boolean => Builder::Boolean(BooleanBuilder::new()),
+            DataType::Int8 => number::<Int8Type>(),
+            DataType::Int16 => number::<Int16Type>(),
+            DataType::Int32 => number::<Int32Type>(),
+            DataType::Int64 => number::<Int64Type>(),
+            DataType::UInt8 => number::<UInt8Type>(),
+            DataType::UInt16 => number::<UInt16Type>(),
+            DataType::UInt32 => number::<UInt32Type>(),
+            DataType::UInt64 => number::<UInt64Type>(),
+            DataType::Float32 => number::<Float32Type>(),
+            DataType::Float64 => number::<Float64Type>(),
+            DataType::Utf8 => Builder::Utf8(StringBuilder::new()),
+            DataType::LargeUtf8 => Builder::LargeUtf8(LargeStringBuilder::new()),
+            DataType::List(item) | DataType::LargeList(item) => Builder::List {
+                offsets: vec![0],
+                nulls: NullBufferBuilder::new(0),
+                item: Box::new(Column::try_new(item.clone())?),
+            },
+            DataType::Struct(fields) => Builder::Struct {
+                columns: fields
+                    .iter()
+                    .cloned()
+                    .map(Column::try_new)
+                    .collect::<Result<_, _>>()?,
+                indices: fields
+                    .iter()
+                    .enumerate()
+                    .map(|(index, field)| (field.name().clone(), index))
+                    .collect(),
+                nulls: NullBufferBuilder::new(0),
+                len: 0,
+            },
+            other => {
+                return Err(ErrorKind::Custom(format!(
+                    "field \"{}\" has the unsupported type {other}",
+                    field.name()
+                )))
+            }
+        };
+        Ok(Self { field, builder })
+    }
+
+    fn len(&self) -> usize {
+        match &self.builder {
+            Builder::Null(len) | Builder::Struct { len, .. } => *len,
+            Builder::Boolean(builder) => builder.len(),
+            Builder::Number(builder) => builder.len(),
+            Builder::Utf8(builder) => builder.len(),
+            Builder::LargeUtf8(builder) => builder.len(),
+            Builder::List { offsets, .. } => offsets.len() - 1,
+        }
+    }
+
+    fn mismatch(&self, found: &str) -> ErrorKind {
+        ErrorKind::Custom(format!(
+            "field \"{}\" of type {} can't be {found}",
+            self.field.name(),
+            self.field.data_type()
+        ))
+    }
+
+    fn append_null(&mut self) -> Result<(), ErrorKind> {
+        if !self.field.is_nullable() {
+            return Err(self.mismatch("null"));
+        }
+        self.push_null();
+        Ok(())
+    }
+
+    /// Appends a null regardless of the field's nullability, e.g. to a child of a null struct
+    fn push_null(&mut self) {
+        match &mut self.builder {
+            Builder::Null(len) => *len += 1,
+            Builder::Boolean(builder) => builder.append_null(),
+            Builder::Number(builder) => builder.append_null(),
+            Builder::Utf8(builder) => builder.append_null(),
+            Builder::LargeUtf8(builder) => builder.append_null(),
+            Builder::List { offsets, nulls, .. } => {
+                offsets.push(*offsets.last().unwrap());
+                nulls.append_null();
+            }
+            Builder::Struct {
+                columns,
+                nulls,
+                len,
+                ..
+            } => {
+                columns.iter_mut().for_each(Column::push_null);
+                nulls.append_null();
+                *len += 1;
+            }
+        }
+    }
+
+    fn append_bool(&mut self, value: bool) -> Result<(), ErrorKind> {
+        match &mut self.builder {
+            Builder::Boolean(builder) => builder.append_value(value),
+            _ => return Err(self.mismatch("a boolean")),
+        }
+        Ok(())
+    }
+
+    fn append_number(&mut self, value: &Number) -> Result<(), ErrorKind> {
+        match &mut self.builder {
+            Builder::Number(builder) => builder.append(value),
+            _ => Err(self.mismatch("a number")),
+        }
+    }
+
+    fn append_str(&mut self, value: &str) -> Result<(), ErrorKind> {
+        match &mut self.builder {
+            Builder::Utf8(builder) => builder.append_value(value),
+            Builder::LargeUtf8(builder) => builder.append_value(value),
+            _ => return Err(self.mismatch("a string")),
+        }
+        Ok(())
+    }
+
+    fn begin_list(&self) -> Result<(), ErrorKind> {
+        match &self.builder {
+            Builder::List { .. } => Ok(()),
+            _ => Err(self.mismatch("an array")),
+        }
+    }
+
+    fn end_list(&mut self) {
+        if let Builder::List {
+            offsets,
+            nulls,
+            item,
+        } = &mut self.builder
+        {
+            offsets.push(item.len());
+            nulls.append_non_null();
+        }
+    }
+
+    /// Returns the number of fields of this column if it is a struct
+    fn begin_struct(&self) -> Result<usize, ErrorKind> {
+        match &self.builder {
+            Builder::Struct { columns, .. } => Ok(columns.len()),
+            _ => Err(self.mismatch("an object")),
+        }
+    }
+
+    /// Ends an object, where `seen` are the fields it contained
+    fn end_struct(&mut self, seen: &[bool]) -> Result<(), ErrorKind> {
+        if let Builder::Struct {
+            columns,
+            nulls,
+            len,
+            ..
+        } = &mut self.builder
+        {
+            for (column, seen) in columns.iter_mut().zip(seen) {
+                if !seen {
+                    if !column.field.is_nullable() {
+                        return Err(ErrorKind::Custom(format!(
+                            "missing field \"{}\"",
+                            column.field.name()
+                        )));
+                    }
+                    column.push_null();
+                }
+            }
+            nulls.append_non_null();
+            *len += 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the index of the field named `key` if this column is a struct
+    fn field_index(&self, key: &str) -> Option<usize> {
+        match &self.builder {
+            Builder::Struct { indices, .. } => indices.get(key).copied(),
+            _ => None,
+        }
+    }
+
+    /// Returns the item column of a list or the `index`th field of a struct
+    fn child(&mut self, index: usize) -> &mut Column {
+        match &mut self.builder {
+            Builder::List { item, .. } => item,
+            Builder::Struct { columns, .. } => &mut columns[index],
+            _ => unreachable!("only lists and structs have children"),
+        }
+    }
+
+    /// Returns the arrays of the fields of this struct column and their length
+    fn finish_struct(&mut self) -> Result<(Vec<ArrayRef>, usize), ArrowError> {
+        match &mut self.builder {
+            Builder::Struct {
+                columns,
+                nulls,
+                len,
+                ..
+            } => {
+                nulls.finish();
+                let arrays = columns
+                    .iter_mut()
+                    .map(Column::finish)
+                    .collect::<Result<_, _>>()?;
+                Ok((arrays, take(len)))
+            }
+            _ => unreachable!("only called for structs"),
+        }
+    }
+
+    fn finish(&mut self) -> Result<ArrayRef, ArrowError> {
+        Ok(match &mut self.builder {
+            Builder::Null(len) => Arc::new(NullArray::new(take(len))),
+            Builder::Boolean(builder) => Arc::new(builder.finish()),
+            Builder::Number(builder) => builder.finish(),
+            Builder::Utf8(builder) => Arc::new(builder.finish()),
+            Builder::LargeUtf8(builder) => Arc::new(builder.finish()),
+            Builder::List {
+                offsets,
+                nulls,
+                item,
+            } => {
+                let offsets = core::mem::replace(offsets, vec![0]);
+                let nulls = nulls.finish();
+                let values = item.finish()?;
+                match self.field.data_type() {
+                    DataType::List(field) => Arc::new(ListArray::try_new(
+                        field.clone(),
+                        offset_buffer(offsets)?,
+                        values,
+                        nulls,
+                    )?),
+                    DataType::LargeList(field) => Arc::new(LargeListArray::try_new(
+                        field.clone(),
+                        offset_buffer(offsets)?,
+                        values,
+                        nulls,
+                    )?),
+                    _ => unreachable!("lists are created from list types"),
+                }
+            }
+            Builder::Struct {
+                columns,
+                nulls,
+                len,
+                ..
+            } => {
+                let DataType::Struct(fields) = self.field.data_type() else {
+                    unreachable!("structs are created from struct types")
+                };
+                let nulls = nulls.finish();
+                let arrays = columns
+                    .iter_mut()
+                    .map(Column::finish)
+                    .collect::<Result<_, _>>()?;
+                Arc::new(StructArray::try_new_with_length(
+                    fields.clone(),
+                    arrays,
+                    nulls,
+                    take(len),
+                )?)
+            }
+        })
+    }
+}
+
+fn offset_buffer<O: TryFrom<usize> + arrow_buffer::ArrowNativeType>(
+    offsets: Vec<usize>,
+) -> Result<OffsetBuffer<O>, ArrowError> {
+    let offsets = offsets
+        .into_iter()
+        .map(|x| O::try_from(x).map_err(|_| ArrowError::OffsetOverflowError(x)))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(OffsetBuffer::new(ScalarBuffer::from(offsets)))
+}
+
+/// An open object or array
+struct Frame {
+    // the index of its column in the column of the parent frame
+    index: usize,
+    kind: Kind,
+}
+
+enum Kind {
+    List,
+    Struct {
+        // whether each field was seen in this object
+        seen: Vec<bool>,
+        // the field of the next value
+        field: usize,
+    },
+}
+
+/// A [`Visitor`] that decodes records into columns
+struct Decoder {
+    schema: SchemaRef,
+    batch_size: usize,
+    duplicate_keys: DuplicateKeys,
+    // the records are the items of a top-level array, or top-level objects otherwise
+    in_array: bool,
+    // whether the top-level array is open
+    in_records: bool,
+    // a struct column of the fields of `schema`
+    root: Column,
+    // the open objects and arrays, starting at the record
+    stack: Vec<Frame>,
+    // whether the next value is skipped, e.g. of an unknown key
+    skip_next: bool,
+    // the number of open containers being skipped
+    skipping: usize,
+    batches: Vec<RecordBatch>,
+}
+
+impl Decoder {
+    fn try_new(
+        schema: SchemaRef,
+        options: &ArrowOptions,
+        in_array: bool,
+    ) -> Result<Self, ErrorKind> {
+        let fields = schema.fields().clone();
+        let root = Column::try_new(Arc::new(Field::new_struct("", fields, false)))?;
+        Ok(Self {
+            schema,
+            batch_size: options.batch_size,
+            duplicate_keys: options.parse.duplicate_keys,
+            in_array,
+            in_records: false,
+            root,
+            stack: vec![],
+            skip_next: false,
+            skipping: 0,
+            batches: vec![],
+        })
+    }
+
+    /// Returns the column of the frame at `depth` of the stack
+    fn column(&mut self, depth: usize) -> &mut Column {
+        self.stack[1..=depth]
+            .iter()
+            .fold(&mut self.root, |column, frame| column.child(frame.index))
+    }
+
+    /// Returns the column of the next value and its index in the column of the innermost frame.
+    /// The stack must not be empty.
+    fn slot(&mut self) -> (usize, &mut Column) {
+        let depth = self.stack.len() - 1;
+        let index = match &self.stack[depth].kind {
+            Kind::List => 0,
+            Kind::Struct { field, .. } => *field,
+        };
+        (index, self.column(depth).child(index))
+    }
+
+    /// Returns the column of the frame just popped from the stack with `index`
+    fn popped(&mut self, index: usize) -> &mut Column {
+        match self.stack.len() {
+            0 => &mut self.root,
+            depth => self.column(depth - 1).child(index),
+        }
+    }
+
+    /// The error of a value found outside of a record
+    fn not_a_record(&self, found: &str) -> ErrorKind {
+        let expected = if self.in_array && !self.in_records {
+            "an array of records"
+        } else {
+            "a record"
+        };
+        ErrorKind::Custom(format!("expected {expected}, found {found}"))
+    }
+
+    /// Returns whether a scalar is skipped
+    fn skip_scalar(&mut self) -> bool {
+        self.skipping > 0 || take(&mut self.skip_next)
+    }
+
+    /// Returns whether the opening of an object or array is skipped
+    fn skip_begin(&mut self) -> bool {
+        let skip = self.skipping > 0 || take(&mut self.skip_next);
+        if skip {
+            self.skipping += 1;
+        }
+        skip
+    }
+
+    /// Returns whether the closing of an object or array is skipped
+    fn skip_end(&mut self) -> bool {
+        let skip = self.skipping > 0;
+        if skip {
+            self.skipping -= 1;
+        }
+        skip
+    }
+
+    fn scalar(
+        &mut self,
+        found: &str,
+        append: impl FnOnce(&mut Column) -> Result<(), ErrorKind>,
+    ) -> Result<(), ErrorKind> {
+        if self.skip_scalar() {
+            return Ok(());
+        }
+        if self.stack.is_empty() {
+            return Err(self.not_a_record(found));
+        }
+        append(self.slot().1)
+    }
+
+    fn flush(&mut self) -> Result<(), ErrorKind> {
+        let (arrays, len) = self.root.finish_struct().map_err(arrow_error)?;
+        let options = RecordBatchOptions::new().with_row_count(Some(len));
+        let batch = RecordBatch::try_new_with_options(self.schema.clone(), arrays, &options)
+            .map_err(arrow_error)?;
+        self.batches.push(batch);
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<Vec<RecordBatch>, ErrorKind> {
+        if self.root.len() > 0 {
+            self.flush()?;
+        }
+        Ok(self.batches)
+    }
+}
+
+impl<'a> Visitor<'a> for Decoder {
+    fn visit_null(&mut self) -> Result<(), ErrorKind> {
+        self.scalar("null", Column::append_null)
+    }
+
+    fn visit_bool(&mut self, value: bool) -> Result<(), ErrorKind> {
+        self.scalar("a boolean", |column| column.append_bool(value))
+    }
+
+    fn visit_number(&mut self, value: Number<'a>) -> Result<(), ErrorKind> {
+        self.scalar("a number", |column| column.append_number(&value))
+    }
+
+    fn visit_str(&mut self, value: Cow<'a, str>) -> Result<(), ErrorKind> {
+        self.scalar("a string", |column| column.append_str(&value))
+    }
+
+    fn begin_object(&mut self) -> Result<(), ErrorKind> {
+        if self.skip_begin() {
+            return Ok(());
+        }
+        let (index, column) = if self.stack.is_empty() {
+            if self.in_array && !self.in_records {
+                return Err(self.not_a_record("an object"));
+            }
+            (0, &mut self.root)
+        } else {
+            self.slot()
+        };
+        let seen = vec![false; column.begin_struct()?];
+        self.stack.push(Frame {
+            index,
+            kind: Kind::Struct { seen, field: 0 },
+        });
+        Ok(())
+    }
+
+    fn key(&mut self, key: Cow<'a, str>) -> Result<(), ErrorKind> {
+        if self.skipping > 0 {
+            return Ok(());
+        }
+        let depth = self.stack.len() - 1;
+        let index = self.column(depth).field_index(&key);
+        let Some(Frame {
+            kind: Kind::Struct { seen, field },
+            ..
+        }) = self.stack.last_mut()
+        else {
+            unreachable!("keys are in objects")
+        };
+        match index {
+            Some(index) if seen[index] => {
+                if self.duplicate_keys != DuplicateKeys::FirstWins {
+                    // a value can't be replaced once appended
+                    return Err(ErrorKind::DuplicateKey(key.into_owned()));
+                }
+                self.skip_next = true;
+            }
+            Some(index) => {
+                seen[index] = true;
+                *field = index;
+            }
+            None => self.skip_next = true,
+        }
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), ErrorKind> {
+        if self.skip_end() {
+            return Ok(());
+        }
+        let Some(Frame {
+            index,
+            kind: Kind::Struct { seen, .. },
+        }) = self.stack.pop()
+        else {
+            unreachable!("objects are closed after being opened")
+        };
+        self.popped(index).end_struct(&seen)?;
+        if self.stack.is_empty() && self.root.len() == self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn begin_array(&mut self) -> Result<(), ErrorKind> {
+        if self.skip_begin() {
+            return Ok(());
+        }
+        if self.stack.is_empty() {
+            if self.in_array && !self.in_records {
+                self.in_records = true;
+                return Ok(());
+            }
+            return Err(self.not_a_record("an array"));
+        }
+        let (index, column) = self.slot();
+        column.begin_list()?;
+        self.stack.push(Frame {
+            index,
+            kind: Kind::List,
+        });
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), ErrorKind> {
+        if self.skip_end() {
+            return Ok(());
+        }
+        match self.stack.pop() {
+            Some(Frame { index, .. }) => self.popped(index).end_list(),
+            None => self.in_records = false,
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,600 @@
+use alloc::borrow::Cow;
+use alloc::string::ToString;
+use core::fmt::Display;
+
+use serde::de::{self, value::BorrowedStrDeserializer, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use super::boolean::{parse_false, parse_true};
+use super::error::*;
+use super::null::parse_null;
+use super::number::parse_number;
+use super::options::ParseOptions;
+use super::parser::{check_depth, current_token, Number};
+use super::relaxed::{is_number_start, parse_any_number, parse_key, skip_ignored};
+use super::string::{parse_single_quoted, parse_string};
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        // the position is set by the `Deserializer` that observes this error
+        Error::new(ErrorKind::Custom(msg.to_string()), 0)
+    }
+}
+
+// with `std`, `serde`'s error trait is `std::error::Error`, implemented in `error.rs`
+#[cfg(not(feature = "std"))]
+impl de::StdError for Error {}
+
+fn is_negative(number: &Number<'_>) -> bool {
+    let (Number::Integer(mantissa, _) | Number::Float(mantissa, _)) = number;
+    mantissa[0] == b'-'
+}
+
+/// Rounds floats that underflow to a zero of the sign of the number
+fn round_underflow<T: Default + core::ops::Neg<Output = T>>(
+    value: Result<T, ErrorKind>,
+    negative: bool,
+) -> Result<T, ErrorKind> {
+    value.or_else(|e| match e {
+        ErrorKind::NumberPrecisionLoss if negative => Ok(-T::default()),
+        ErrorKind::NumberPrecisionLoss => Ok(T::default()),
+        e => Err(e),
+    })
+}
+
+/// A [`serde::Deserializer`] that reads JSON from bytes without an intermediary [`Value`](crate::Value).
+///
+/// Strings without escapes are borrowed from the input and can be deserialized into `&'de str`.
+pub struct Deserializer<'de> {
+    json: &'de [u8],
+    values: &'de [u8],
+    options: ParseOptions,
+    depth: usize,
+}
+
+impl<'de> Deserializer<'de> {
+    /// Returns a new [`Deserializer`] of `json`
+    pub fn from_slice(json: &'de [u8]) -> Self {
+        Self::with_options(json, ParseOptions::default())
+    }
+
+    /// Returns a new [`Deserializer`] of `json` that parses according to `options`
+    pub fn with_options(json: &'de [u8], options: ParseOptions) -> Self {
+        Self {
+            json,
+            values: json,
+            options,
+            depth: 0,
+        }
+    }
+
+    /// Errors if there is anything other than whitespace (and comments with
+    /// [`ParseOptions::relaxed`]) left to deserialize.
+    pub fn end(&mut self) -> Result<(), Error> {
+        skip_ignored(&mut self.values, &self.options).map_err(|e| self.error(e))?;
+        if self.values.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::InvalidEOF))
+        }
+    }
+
+    #[inline]
+    fn error(&self, kind: ErrorKind) -> Error {
+        kind.locate(self.json, self.values)
+    }
+
+    /// Sets the position of errors created by visitors via [`de::Error::custom`]
+    #[inline]
+    fn fix_position(&self, error: Error) -> Error {
+        if matches!(error.kind(), ErrorKind::Custom(_)) && error.offset() == 0 {
+            Error::new(error.kind().clone(), self.json.len() - self.values.len())
+        } else {
+            error
+        }
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Result<u8, Error> {
+        skip_ignored(&mut self.values, &self.options).map_err(|e| self.error(e))?;
+        current_token(self.values).map_err(|e| self.error(e))
+    }
+
+    /// consumes `expected` or errors with `error`
+    #[inline]
+    fn expect(&mut self, expected: u8, error: fn(u8) -> ErrorKind) -> Result<(), Error> {
+        let token = self.peek()?;
+        if token == expected {
+            self.values = &self.values[1..];
+            Ok(())
+        } else {
+            Err(self.error(error(token)))
+        }
+    }
+
+    /// Consumes `end`, the closing token of an array or object, after a trailing comma with
+    /// [`ParseOptions::relaxed`], e.g. of a tuple or enum that reads no further items
+    #[inline]
+    fn close(&mut self, end: u8) -> Result<(), Error> {
+        if self.options.relaxed && self.peek()? == b',' {
+            self.values = &self.values[1..];
+        }
+        self.expect(end, ErrorKind::InvalidToken)
+    }
+
+    /// Consumes the opening token of an array or object, increasing the depth
+    #[inline]
+    fn enter(&mut self) -> Result<(), Error> {
+        check_depth(&self.options, self.depth).map_err(|e| self.error(e))?;
+        self.depth += 1;
+        self.values = &self.values[1..];
+        Ok(())
+    }
+
+    /// Parses the key of an object
+    #[inline]
+    fn key(&mut self) -> Result<Cow<'de, str>, Error> {
+        self.peek()?;
+        parse_key(&mut self.values, &self.options).map_err(|e| self.error(e))
+    }
+
+    /// Visits the next value with `visit` if it is a number, or like
+    /// [`de::Deserializer::deserialize_any`] otherwise
+    fn deserialize_number<V: Visitor<'de>>(
+        &mut self,
+        visitor: V,
+        visit: fn(&Self, Number<'_>, V) -> Result<V::Value, Error>,
+    ) -> Result<V::Value, Error> {
+        match self.peek()? {
+            token if is_number_start(token, &self.options) => {
+                let number =
+                    parse_any_number(&mut self.values, &self.options).map_err(|e| self.error(e))?;
+                visit(self, number, visitor).map_err(|e| self.fix_position(e))
+            }
+            _ => de::Deserializer::deserialize_any(self, visitor),
+        }
+    }
+
+    /// Visits `number` as the first of `i64`, `u64` and `f64` that represents it
+    fn visit_number<V: Visitor<'de>>(
+        &self,
+        number: Number<'_>,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let negative = is_negative(&number);
+
+        // integers that do not fit in 64 bits are deserialized as floats
+        if let Number::Integer(..) = number {
+            if negative {
+                if let Ok(value) = number.to_i64() {
+                    return visitor.visit_i64(value);
+                }
+            } else if let Ok(value) = number.to_u64() {
+                return visitor.visit_u64(value);
+            }
+        }
+        let value = round_underflow(number.to_f64(), negative).map_err(|e| self.error(e))?;
+        visitor.visit_f64(value)
+    }
+
+    /// Visits `number` as an `i128` if it is an integer in its range, e.g. beyond the range of
+    /// `i64`, or like [`Deserializer::visit_number`] otherwise
+    fn visit_i128<V: Visitor<'de>>(
+        &self,
+        number: Number<'_>,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match number {
+            Number::Integer(..) => match number.to_i128() {
+                Ok(value) => visitor.visit_i128(value),
+                Err(_) => self.visit_number(number, visitor),
+            },
+            Number::Float(..) => self.visit_number(number, visitor),
+        }
+    }
+
+    /// Visits `number` as a `u128` if it is an integer in its range, e.g. beyond the range of
+    /// `u64`, or like [`Deserializer::visit_number`] otherwise
+    fn visit_u128<V: Visitor<'de>>(
+        &self,
+        number: Number<'_>,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        match number {
+            Number::Integer(..) => match number.to_u128() {
+                Ok(value) => visitor.visit_u128(value),
+                Err(_) => self.visit_number(number, visitor),
+            },
+            Number::Float(..) => self.visit_number(number, visitor),
+        }
+    }
+
+    /// Visits `number` as an `f32`, so that numbers larger than [`f32::MAX`] error instead of
+    /// becoming infinite
+    fn visit_f32<V: Visitor<'de>>(
+        &self,
+        number: Number<'_>,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let negative = is_negative(&number);
+        let value = round_underflow(number.to_f32(), negative).map_err(|e| self.error(e))?;
+        visitor.visit_f32(value)
+    }
+}
+
+/// Deserializes an instance of `T` from JSON bytes
+/// # Errors
+/// If `json` is not valid JSON or it does not represent `T`.
+pub fn from_slice<'a, T: de::Deserialize<'a>>(json: &'a [u8]) -> Result<T, Error> {
+    from_slice_with_options(json, &ParseOptions::default())
+}
+
+/// Deserializes an instance of `T` from JSON bytes according to `options`
+/// # Errors
+/// If `json` is not valid JSON, it does not fulfill `options` or it does not represent `T`.
+pub fn from_slice_with_options<'a, T: de::Deserialize<'a>>(
+    json: &'a [u8],
+    options: &ParseOptions,
+) -> Result<T, Error> {
+    let mut deserializer = Deserializer::with_options(json, options.clone());
+    let value = T::deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(value)
+}
+
+impl<'de> de::Deserializer<'de> for &mut Deserializer<'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let value = match self.peek()? {
+            b'{' => {
+                self.enter()?;
+                let value = visitor.visit_map(MapAccess::new(self));
+                self.depth -= 1;
+                value.and_then(|value| {
+                    self.close(b'}')?;
+                    Ok(value)
+                })
+            }
+            b'[' => {
+                self.enter()?;
+                let value = visitor.visit_seq(SeqAccess::new(self));
+                self.depth -= 1;
+                value.and_then(|value| {
+                    self.close(b']')?;
+                    Ok(value)
+                })
+            }
+            b'"' => {
+                let string = parse_string(&mut self.values).map_err(|e| self.error(e))?;
+                visit_str(string, visitor)
+            }
+            b'\'' if self.options.relaxed => {
+                let string = parse_single_quoted(&mut self.values).map_err(|e| self.error(e))?;
+                visit_str(string, visitor)
+            }
+            b'n' => {
+                parse_null(&mut self.values).map_err(|e| self.error(e))?;
+                visitor.visit_unit()
+            }
+            b't' => {
+                parse_true(&mut self.values).map_err(|e| self.error(e))?;
+                visitor.visit_bool(true)
+            }
+            b'f' => {
+                parse_false(&mut self.values).map_err(|e| self.error(e))?;
+                visitor.visit_bool(false)
+            }
+            token if is_number_start(token, &self.options) => {
+                let number =
+                    parse_any_number(&mut self.values, &self.options).map_err(|e| self.error(e))?;
+                self.visit_number(number, visitor)
+            }
+            other => return Err(self.error(ErrorKind::InvalidToken(other))),
+        };
+        value.map_err(|e| self.fix_position(e))
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_number(visitor, Deserializer::visit_f32)
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_number(visitor, Deserializer::visit_i128)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_number(visitor, Deserializer::visit_u128)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        if self.peek()? == b'n' {
+            parse_null(&mut self.values).map_err(|e| self.error(e))?;
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        let value = match self.peek()? {
+            // unit variant, e.g. `"A"`
+            b'"' => {
+                let string = parse_string(&mut self.values).map_err(|e| self.error(e))?;
+                visit_unit_variant(string, visitor)
+            }
+            b'\'' if self.options.relaxed => {
+                let string = parse_single_quoted(&mut self.values).map_err(|e| self.error(e))?;
+                visit_unit_variant(string, visitor)
+            }
+            // other variants, e.g. `{"A": 1}`
+            b'{' => {
+                self.enter()?;
+                let value = visitor.visit_enum(VariantAccess { de: self });
+                self.depth -= 1;
+                value.and_then(|value| {
+                    self.close(b'}')?;
+                    Ok(value)
+                })
+            }
+            other => return Err(self.error(ErrorKind::InvalidToken(other))),
+        };
+        value.map_err(|e| self.fix_position(e))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct SeqAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    first: bool,
+}
+
+impl<'a, 'de> SeqAccess<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        Self { de, first: true }
+    }
+}
+
+impl<'de> de::SeqAccess<'de> for SeqAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Error> {
+        let token = self.de.peek()?;
+        if token == b']' {
+            return Ok(None);
+        }
+        if !self.first {
+            if token != b',' {
+                return Err(self.de.error(ErrorKind::MissingComma(token)));
+            }
+            self.de.values = &self.de.values[1..];
+            // a trailing comma, whose `]` is consumed by the array
+            if self.de.options.relaxed && self.de.peek()? == b']' {
+                return Ok(None);
+            }
+        }
+        self.first = false;
+        seed.deserialize(&mut *self.de).map(Some)
+    }
+}
+
+struct MapAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+    first: bool,
+}
+
+impl<'a, 'de> MapAccess<'a, 'de> {
+    fn new(de: &'a mut Deserializer<'de>) -> Self {
+        Self { de, first: true }
+    }
+}
+
+impl<'de> de::MapAccess<'de> for MapAccess<'_, 'de> {
+    type Error = Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Error> {
+        let token = self.de.peek()?;
+        if token == b'}' {
+            return Ok(None);
+        }
+        if !self.first {
+            if token != b',' {
+                return Err(self.de.error(ErrorKind::MissingComma(token)));
+            }
+            self.de.values = &self.de.values[1..];
+            // a trailing comma, whose `}` is consumed by the object
+            if self.de.options.relaxed && self.de.peek()? == b'}' {
+                return Ok(None);
+            }
+        }
+        self.first = false;
+
+        let key = self.de.key()?;
+        seed.deserialize(MapKey { de: self.de, key }).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Error> {
+        self.de.expect(b':', ErrorKind::InvalidToken)?;
+        seed.deserialize(&mut *self.de)
+    }
+}
+
+/// Deserializes a key of an object, a string, also into numbers and booleans, e.g. `{"1": 2}`
+/// into a `HashMap<u32, u32>`
+struct MapKey<'a, 'de> {
+    de: &'a Deserializer<'de>,
+    key: Cow<'de, str>,
+}
+
+impl<'de> MapKey<'_, 'de> {
+    /// Visits the key as a number with `visit` if it is one, or as a string otherwise
+    fn deserialize_number<V: Visitor<'de>>(
+        self,
+        visitor: V,
+        visit: fn(&Deserializer<'de>, Number<'_>, V) -> Result<V::Value, Error>,
+    ) -> Result<V::Value, Error> {
+        let mut digits = self.key.as_bytes();
+        let number = match digits.first() {
+            Some(b'0'..=b'9' | b'-') => {
+                parse_number(&mut digits).ok().filter(|_| digits.is_empty())
+            }
+            _ => None,
+        };
+        let value = match number {
+            Some(number) => visit(self.de, number, visitor),
+            // e.g. `"a"` into an integer, which errors with the expected type
+            None => visit_str(self.key, visitor),
+        };
+        value.map_err(|e| self.de.fix_position(e))
+    }
+}
+
+fn visit_str<'de, V: Visitor<'de>>(string: Cow<'de, str>, visitor: V) -> Result<V::Value, Error> {
+    match string {
+        Cow::Borrowed(string) => visitor.visit_borrowed_str(string),
+        Cow::Owned(string) => visitor.visit_string(string),
+    }
+}
+
+fn visit_unit_variant<'de, V: Visitor<'de>>(
+    variant: Cow<'de, str>,
+    visitor: V,
+) -> Result<V::Value, Error> {
+    match variant {
+        Cow::Borrowed(variant) => visitor.visit_enum(BorrowedStrDeserializer::new(variant)),
+        Cow::Owned(variant) => {
+            visitor.visit_enum(IntoDeserializer::<Error>::into_deserializer(variant))
+        }
+    }
+}
+
+macro_rules! deserialize_number_key {
+    ($($method:ident)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+                self.deserialize_number(visitor, Deserializer::visit_number)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for MapKey<'_, 'de> {
+    type Error = Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        visit_str(self.key, visitor).map_err(|e| self.de.fix_position(e))
+    }
+
+    deserialize_number_key! {
+        deserialize_i8 deserialize_i16 deserialize_i32 deserialize_i64
+        deserialize_u8 deserialize_u16 deserialize_u32 deserialize_u64
+        deserialize_f64
+    }
+
+    fn deserialize_i128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_number(visitor, Deserializer::visit_i128)
+    }
+
+    fn deserialize_u128<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_number(visitor, Deserializer::visit_u128)
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        self.deserialize_number(visitor, Deserializer::visit_f32)
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+        let value = match self.key.as_ref() {
+            "true" => visitor.visit_bool(true),
+            "false" => visitor.visit_bool(false),
+            _ => visit_str(self.key, visitor),
+        };
+        value.map_err(|e| self.de.fix_position(e))
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        visit_unit_variant(self.key, visitor).map_err(|e| self.de.fix_position(e))
+    }
+
+    forward_to_deserialize_any! {
+        char str string bytes byte_buf option unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+struct VariantAccess<'a, 'de> {
+    de: &'a mut Deserializer<'de>,
+}
+
+impl<'de> de::EnumAccess<'de> for VariantAccess<'_, 'de> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self), Error> {
+        let key = self.de.key()?;
+        let variant = seed.deserialize(MapKey { de: self.de, key })?;
+        self.de.expect(b':', ErrorKind::InvalidToken)?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for VariantAccess<'_, 'de> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<(), Error> {
+        de::Deserialize::deserialize(self.de)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Error> {
+        seed.deserialize(self.de)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_seq(self.de, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error> {
+        de::Deserializer::deserialize_map(self.de, visitor)
+    }
+}
@@ -1,8 +1,11 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
 use core::fmt::Display;
 
 /// List of possible errors
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Error {
+#[non_exhaustive]
+pub enum ErrorKind {
     /// todo
     NumberWithLeadingZero,
     /// todo
@@ -39,10 +42,121 @@ pub enum Error {
     InvalidTrueToken([u8; 4]),
     /// todo
     InvalidEOF,
+    /// When arrays and objects are nested deeper than [`ParseOptions::max_depth`](crate::ParseOptions::max_depth)
+    RecursionLimitExceeded,
+    /// When a number does not fit in the type it is converted to
+    NumberOverflow,
+    /// When a number can't be converted to a type without losing precision, e.g. `1.5` to an integer
+    NumberPrecisionLoss,
+    /// An error raised while deserializing into a type, e.g. a missing field
+    Custom(String),
+    /// When a key is repeated in an object and [`DuplicateKeys::Error`](crate::DuplicateKeys::Error)
+    /// is used. Contains the key.
+    DuplicateKey(String),
+    /// When an option of [`ParseOptions`](crate::ParseOptions) is not supported by the reader it
+    /// is passed to. Contains the name of the option.
+    UnsupportedOption(&'static str),
+}
+
+impl ErrorKind {
+    /// Returns an [`Error`] located at `remaining`, a suffix of `json`.
+    #[inline]
+    pub(crate) fn locate(self, json: &[u8], remaining: &[u8]) -> Error {
+        Error::new(self, json.len() - remaining.len())
+    }
+}
+
+/// An error together with the byte offset of the input where it happened
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Error {
+    kind: ErrorKind,
+    offset: usize,
+}
+
+impl Error {
+    /// Returns a new [`Error`] of `kind` at byte `offset`.
+    pub fn new(kind: ErrorKind, offset: usize) -> Self {
+        Self { kind, offset }
+    }
+
+    /// The kind of this error
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    /// The byte offset into the parsed input where this error happened
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the [`Position`] of this error in `json`, the input that was parsed.
+    pub fn position<'a>(&self, json: &'a [u8]) -> Position<'a> {
+        position(json, self.offset)
+    }
 }
 
 impl Display for Error {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        write!(f, "{:?}", self)
+        write!(f, "{:?} at byte {}", self.kind, self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+/// A human-readable location in the input
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position<'a> {
+    /// The line number, starting at 1
+    pub line: usize,
+    /// The column number in characters, starting at 1
+    pub column: usize,
+    /// A short excerpt of the line around the position
+    pub excerpt: Cow<'a, str>,
+}
+
+// number of bytes on each side of the offset included in the excerpt
+const EXCERPT_RADIUS: usize = 20;
+
+/// Returns the line, column and an excerpt of `json` at byte `offset`.
+/// Offsets past the end of `json` are clamped to its end.
+pub fn position(json: &[u8], offset: usize) -> Position<'_> {
+    let offset = offset.min(json.len());
+    let before = &json[..offset];
+
+    let line_start = before
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map(|x| x + 1)
+        .unwrap_or(0);
+    let line_end = json[offset..]
+        .iter()
+        .position(|byte| *byte == b'\n')
+        .map(|x| x + offset)
+        .unwrap_or(json.len());
+
+    let line = before.iter().filter(|byte| **byte == b'\n').count() + 1;
+    // utf-8 continuation bytes do not start a new character
+    let column = json[line_start..offset]
+        .iter()
+        .filter(|byte| (**byte & 0b1100_0000) != 0b1000_0000)
+        .count()
+        + 1;
+
+    let mut start = offset.saturating_sub(EXCERPT_RADIUS).max(line_start);
+    while start > line_start && (json[start] & 0b1100_0000) == 0b1000_0000 {
+        start -= 1;
+    }
+    let mut end = (offset + EXCERPT_RADIUS).min(line_end);
+    while end < line_end && (json[end] & 0b1100_0000) == 0b1000_0000 {
+        end += 1;
+    }
+    let excerpt = &json[start..end];
+    let excerpt = excerpt.strip_suffix(b"\r").unwrap_or(excerpt);
+
+    Position {
+        line,
+        column,
+        excerpt: String::from_utf8_lossy(excerpt),
     }
 }
@@ -0,0 +1,312 @@
+use alloc::vec::Vec;
+
+use super::error::*;
+use super::number::{next_state, State as NumberState};
+use super::options::ParseOptions;
+use super::parser::{check_depth, skip_unused};
+use super::reader::{parse_scalar, Container, Event};
+use super::string::parse_string;
+
+/// The result of [`IncrementalReader::next_event`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status<'a> {
+    /// The next event of the document
+    Event(Event<'a>),
+    /// The input fed so far ends before the next event is complete.
+    /// Feed more input with [`IncrementalReader::feed`], or call [`IncrementalReader::finish`]
+    /// if there is none.
+    NeedMoreInput,
+    /// The document was fully read, or an error was returned
+    Finished,
+}
+
+/// What the reader expects next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Value,
+    // a value or `]`
+    FirstItem,
+    // a key or `}`
+    FirstKey,
+    // `,` or the end of the current container
+    Separator,
+    Key,
+    Colon,
+    // whitespace after the document
+    End,
+}
+
+/// The state of the string lexer, mirroring how strings are delimited while parsing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StringState {
+    Normal,
+    Escape,
+    // the number of remaining hex digits of a `\u` escape
+    Unicode(u8),
+}
+
+/// How much of an incomplete token was already scanned, so that it is not scanned again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Scan {
+    None,
+    String(StringState, usize),
+    Number(NumberState, usize),
+}
+
+/// A reader of a JSON document that arrives in chunks, yielding the same [`Event`]s and errors
+/// as [`Reader`](crate::Reader) does for the whole document.
+///
+/// Only the input that was not yet consumed is kept in memory, i.e. at most one incomplete token
+/// after all available events are read.
+#[derive(Debug, Clone, Default)]
+pub struct IncrementalReader {
+    buffer: Vec<u8>,
+    state: State,
+}
+
+#[derive(Debug, Clone)]
+struct State {
+    options: ParseOptions,
+    // the number of bytes consumed before `buffer`
+    offset: usize,
+    // the number of bytes of `buffer` consumed
+    position: usize,
+    stack: Vec<Container>,
+    expect: Expect,
+    scan: Scan,
+    // whether all input was fed
+    eof: bool,
+    finished: bool,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        Self {
+            options: ParseOptions::default(),
+            offset: 0,
+            position: 0,
+            stack: vec![],
+            expect: Expect::Value,
+            scan: Scan::None,
+            eof: false,
+            finished: false,
+        }
+    }
+}
+
+impl IncrementalReader {
+    /// Returns a new [`IncrementalReader`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new [`IncrementalReader`] that parses according to `options`
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            buffer: vec![],
+            state: State {
+                options,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Appends `chunk` to the input
+    pub fn feed(&mut self, chunk: &[u8]) {
+        // drop the input that was already consumed
+        self.buffer.drain(..self.state.position);
+        self.state.offset += self.state.position;
+        self.state.position = 0;
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Declares that all input was fed, so that the input ending is no longer
+    /// [`Status::NeedMoreInput`] but either the end of the document or an error.
+    pub fn finish(&mut self) {
+        self.state.eof = true;
+    }
+
+    /// The number of bytes of the input consumed so far.
+    pub fn byte_offset(&self) -> usize {
+        self.state.offset + self.state.position
+    }
+
+    /// Returns the next event, borrowing from the input when possible.
+    /// # Errors
+    /// If the input is not valid JSON or it does not fulfill the options.
+    /// The error is located in the whole input fed so far.
+    /// [`ParseOptions::relaxed`] is not supported and fails with
+    /// [`ErrorKind::UnsupportedOption`] on the first call.
+    pub fn next_event(&mut self) -> Result<Status<'_>, Error> {
+        self.state.next(&self.buffer)
+    }
+}
+
+impl State {
+    fn next<'a>(&mut self, buffer: &'a [u8]) -> Result<Status<'a>, Error> {
+        if self.finished {
+            return Ok(Status::Finished);
+        }
+        let mut values = &buffer[self.position..];
+        let result = if self.options.relaxed {
+            Err(ErrorKind::UnsupportedOption("relaxed"))
+        } else {
+            self.step(&mut values)
+        };
+        self.position = buffer.len() - values.len();
+        result.map_err(|e| {
+            self.finished = true;
+            Error::new(e, self.offset + self.position)
+        })
+    }
+
+    fn step<'a>(&mut self, values: &mut &'a [u8]) -> Result<Status<'a>, ErrorKind> {
+        loop {
+            skip_unused(values);
+            let Some(&token) = values.first() else {
+                return if !self.eof {
+                    Ok(Status::NeedMoreInput)
+                } else if self.expect == Expect::End {
+                    self.finished = true;
+                    Ok(Status::Finished)
+                } else {
+                    Err(ErrorKind::InvalidEOF)
+                };
+            };
+
+            match self.expect {
+                Expect::Value => return self.value(values, token),
+                Expect::FirstItem | Expect::FirstKey | Expect::Separator => {
+                    let container = *self.stack.last().unwrap();
+                    let end = match container {
+                        Container::Array => b']',
+                        Container::Object => b'}',
+                    };
+                    if token == end {
+                        *values = &values[1..];
+                        self.stack.pop();
+                        self.end_value();
+                        return Ok(Status::Event(match container {
+                            Container::Array => Event::EndArray,
+                            Container::Object => Event::EndObject,
+                        }));
+                    }
+                    if self.expect == Expect::Separator {
+                        if token != b',' {
+                            return Err(ErrorKind::MissingComma(token));
+                        }
+                        *values = &values[1..]; // consume ","
+                    }
+                    self.expect = match container {
+                        Container::Array => Expect::Value,
+                        Container::Object => Expect::Key,
+                    };
+                }
+                Expect::Key => {
+                    if token != b'"' {
+                        return Err(ErrorKind::InvalidStringToken(token));
+                    }
+                    if !self.is_complete(values) {
+                        return Ok(Status::NeedMoreInput);
+                    }
+                    let key = parse_string(values)?;
+                    self.expect = Expect::Colon;
+                    return Ok(Status::Event(Event::Key(key)));
+                }
+                Expect::Colon => {
+                    if token != b':' {
+                        return Err(ErrorKind::InvalidToken(token));
+                    }
+                    *values = &values[1..];
+                    self.expect = Expect::Value;
+                }
+                Expect::End => return Err(ErrorKind::InvalidEOF),
+            }
+        }
+    }
+
+    fn value<'a>(&mut self, values: &mut &'a [u8], token: u8) -> Result<Status<'a>, ErrorKind> {
+        let (container, expect, event) = match token {
+            b'{' => (Container::Object, Expect::FirstKey, Event::StartObject),
+            b'[' => (Container::Array, Expect::FirstItem, Event::StartArray),
+            _ => {
+                if !self.is_complete(values) {
+                    return Ok(Status::NeedMoreInput);
+                }
+                let event = parse_scalar(values, token, &self.options)?;
+                self.end_value();
+                return Ok(Status::Event(event));
+            }
+        };
+        check_depth(&self.options, self.stack.len())?;
+        *values = &values[1..];
+        self.stack.push(container);
+        self.expect = expect;
+        Ok(Status::Event(event))
+    }
+
+    /// Updates what is expected after a value
+    #[inline]
+    fn end_value(&mut self) {
+        self.expect = if self.stack.is_empty() {
+            Expect::End
+        } else {
+            Expect::Separator
+        };
+    }
+
+    /// Returns whether the token at the start of `values` is complete, i.e. whether parsing it
+    /// does not depend on input that was not fed yet.
+    fn is_complete(&mut self, values: &[u8]) -> bool {
+        if self.eof {
+            return true;
+        }
+        let complete = match values[0] {
+            b'"' => {
+                let (mut state, mut i) = match self.scan {
+                    Scan::String(state, i) => (state, i),
+                    _ => (StringState::Normal, 1),
+                };
+                loop {
+                    let Some(byte) = values.get(i) else {
+                        self.scan = Scan::String(state, i);
+                        break false;
+                    };
+                    i += 1;
+                    state = match (state, byte) {
+                        (StringState::Normal, b'"') => break true,
+                        (StringState::Normal, b'\\') => StringState::Escape,
+                        (StringState::Escape, b'u') => StringState::Unicode(4),
+                        (StringState::Unicode(n), _) if n > 1 => StringState::Unicode(n - 1),
+                        _ => StringState::Normal,
+                    };
+                }
+            }
+            b'0'..=b'9' | b'-' => {
+                let (mut state, mut i) = match self.scan {
+                    Scan::Number(state, i) => (state, i),
+                    _ => (NumberState::Start, 0),
+                };
+                loop {
+                    let Some(byte) = values.get(i) else {
+                        self.scan = Scan::Number(state, i);
+                        break false;
+                    };
+                    // errors are complete tokens, they are returned when parsing
+                    match next_state(*byte, state) {
+                        Ok(NumberState::Finished) | Err(_) => break true,
+                        Ok(next) => state = next,
+                    }
+                    i += 1;
+                }
+            }
+            b'n' | b't' => values.len() >= 4,
+            b'f' => values.len() >= 5,
+            _ => true,
+        };
+        if complete {
+            self.scan = Scan::None;
+        }
+        complete
+    }
+}
@@ -0,0 +1,405 @@
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::mem::take;
+
+use super::error::*;
+use super::ndjson::for_each_line;
+use super::options::ParseOptions;
+use super::parser::{Number, Object, Value};
+use super::visitor::{visit_with_options, Visitor};
+
+/// The type of JSON values inferred by [`Inference`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum InferredType {
+    /// Only `null`s (or no values) were seen
+    #[default]
+    Null,
+    /// Booleans
+    Boolean,
+    /// Numbers, all of them [`Number::Integer`]
+    Integer,
+    /// Numbers, some of them [`Number::Float`]
+    Float,
+    /// Strings
+    String,
+    /// Arrays whose items are of the field's type. The field is named `item`.
+    List(Box<InferredField>),
+    /// Objects with the fields, in the order they were first seen (for [`Value`]s, in the
+    /// order of [`Object`]'s iteration)
+    Struct(Vec<InferredField>),
+    /// Values of different kinds, e.g. strings and numbers, with one type per kind in the order
+    /// they were first seen. Never contains [`InferredType::Null`] nor unions.
+    Union(Vec<InferredType>),
+}
+
+/// A field of an [`InferredType`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InferredField {
+    /// The key of the field
+    pub name: String,
+    /// The type of its values
+    pub data_type: InferredType,
+    /// Whether it was `null` or missing in some values
+    pub nullable: bool,
+}
+
+impl InferredField {
+    fn new(name: String, data_type: InferredType) -> Self {
+        let nullable = data_type == InferredType::Null;
+        Self {
+            name,
+            data_type,
+            nullable,
+        }
+    }
+
+    fn item() -> Self {
+        Self {
+            name: "item".into(),
+            data_type: InferredType::Null,
+            nullable: false,
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        self.nullable |= other.nullable;
+        self.data_type = take(&mut self.data_type).merge(other.data_type);
+    }
+
+    /// Returns this field as [`InferredType::to_value`] describes
+    pub fn to_value(&self) -> Value<'static> {
+        object([
+            ("name", Value::String(Cow::Owned(self.name.clone()))),
+            ("nullable", Value::Bool(self.nullable)),
+            ("type", self.data_type.to_value()),
+        ])
+    }
+}
+
+fn object<const N: usize>(items: [(&'static str, Value<'static>); N]) -> Value<'static> {
+    Value::Object(
+        items
+            .into_iter()
+            .map(|(key, value)| (Cow::Borrowed(key), value))
+            .collect::<Object>(),
+    )
+}
+
+/// Adds `field` to `fields`, merging it with the field of the same name if any
+fn add_field(fields: &mut Vec<InferredField>, field: InferredField) {
+    match fields.iter_mut().find(|x| x.name == field.name) {
+        Some(existing) => existing.merge(field),
+        None => fields.push(field),
+    }
+}
+
+impl InferredType {
+    /// The name of the kind of this type, where integers and floats are both numbers
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Integer | Self::Float => "number",
+            Self::String => "string",
+            Self::List(_) => "list",
+            Self::Struct(_) => "struct",
+            Self::Union(_) => "union",
+        }
+    }
+
+    /// Returns the type of both `self` and `other`
+    fn merge(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Null, other) | (other, Self::Null) => other,
+            (Self::Integer, Self::Float) | (Self::Float, Self::Integer) => Self::Float,
+            (Self::List(mut item), Self::List(other)) => {
+                item.merge(*other);
+                Self::List(item)
+            }
+            (Self::Struct(mut fields), Self::Struct(other)) => {
+                // fields missing on either side are nullable
+                for field in fields.iter_mut() {
+                    if !other.iter().any(|x| x.name == field.name) {
+                        field.nullable = true;
+                    }
+                }
+                for mut field in other {
+                    if !fields.iter().any(|x| x.name == field.name) {
+                        field.nullable = true;
+                    }
+                    add_field(&mut fields, field);
+                }
+                Self::Struct(fields)
+            }
+            (Self::Union(types), other) => Self::Union(add_to_union(types, other)),
+            (first, Self::Union(types)) => {
+                Self::Union(types.into_iter().fold(vec![first], add_to_union))
+            }
+            (first, other) if first.kind() == other.kind() => first,
+            (first, other) => Self::Union(vec![first, other]),
+        }
+    }
+
+    /// Returns the type of `value`
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(_) => Self::Boolean,
+            Value::Number(Number::Integer(..)) => Self::Integer,
+            Value::Number(Number::Float(..)) => Self::Float,
+            Value::String(_) => Self::String,
+            Value::Array(items) => {
+                let mut item = InferredField::item();
+                for value in items {
+                    item.merge(InferredField::new(String::new(), Self::of(value)));
+                }
+                Self::List(Box::new(item))
+            }
+            Value::Object(object) => Self::Struct(
+                object
+                    .iter()
+                    .map(|(key, value)| InferredField::new(key.to_string(), Self::of(value)))
+                    .collect(),
+            ),
+            Value::MultiObject(entries) => {
+                let mut fields = vec![];
+                for (key, value) in entries {
+                    add_field(
+                        &mut fields,
+                        InferredField::new(key.to_string(), Self::of(value)),
+                    );
+                }
+                Self::Struct(fields)
+            }
+        }
+    }
+
+    /// Returns a description of this type as a [`Value`], e.g. to write it with
+    /// [`to_string`](crate::to_string).
+    ///
+    /// Scalar types are the strings `"null"`, `"boolean"`, `"integer"`, `"float"` and
+    /// `"string"`. Other types are objects: `{"type": "list", "item": field}`,
+    /// `{"type": "struct", "fields": [field, ...]}` and `{"type": "union", "types": [type, ...]}`,
+    /// where each field is `{"name": name, "nullable": bool, "type": type}`.
+    pub fn to_value(&self) -> Value<'static> {
+        let name = |name| Value::String(Cow::Borrowed(name));
+        match self {
+            Self::Null => name("null"),
+            Self::Boolean => name("boolean"),
+            Self::Integer => name("integer"),
+            Self::Float => name("float"),
+            Self::String => name("string"),
+            Self::List(item) => object([("type", name("list")), ("item", item.to_value())]),
+            Self::Struct(fields) => object([
+                ("type", name("struct")),
+                (
+                    "fields",
+                    Value::Array(fields.iter().map(InferredField::to_value).collect()),
+                ),
+            ]),
+            Self::Union(types) => object([
+                ("type", name("union")),
+                (
+                    "types",
+                    Value::Array(types.iter().map(InferredType::to_value).collect()),
+                ),
+            ]),
+        }
+    }
+}
+
+/// Adds `data_type` to the members of a union, merging it with the member of the same kind
+fn add_to_union(mut types: Vec<InferredType>, data_type: InferredType) -> Vec<InferredType> {
+    match data_type {
+        InferredType::Null => {}
+        InferredType::Union(other) => return other.into_iter().fold(types, add_to_union),
+        data_type => match types.iter_mut().find(|x| x.kind() == data_type.kind()) {
+            Some(member) => *member = take(member).merge(data_type),
+            None => types.push(data_type),
+        },
+    }
+    types
+}
+
+/// Infers the type of JSON values from many examples, e.g. the records of a dataset, by
+/// merging the type of each example:
+/// * integers and floats are floats
+/// * fields of objects and items of arrays that are `null` or missing in some examples are
+///   nullable
+/// * values of different kinds, e.g. numbers and strings, are unions
+///
+/// Examples can be [`Value`]s or JSON, which is inferred without building [`Value`]s.
+#[derive(Debug, Clone, Default)]
+pub struct Inference {
+    data_type: InferredType,
+    // the open objects and arrays when inferring JSON
+    stack: Vec<Partial>,
+}
+
+/// The type of an object or array being visited
+#[derive(Debug, Clone)]
+enum Partial {
+    List(InferredField),
+    Struct(Vec<InferredField>, String),
+}
+
+impl Inference {
+    /// Returns a new [`Inference`] without examples
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `value` as an example
+    pub fn add_value(&mut self, value: &Value) {
+        self.data_type = take(&mut self.data_type).merge(InferredType::of(value));
+    }
+
+    /// Adds the JSON document `json` as an example
+    /// # Errors
+    /// If `json` is not valid JSON, in which case no example is added.
+    pub fn add_json(&mut self, json: &[u8]) -> Result<(), Error> {
+        self.add_json_with_options(json, &ParseOptions::default())
+    }
+
+    /// Adds the JSON document `json`, parsed according to `options`, as an example
+    /// # Errors
+    /// If `json` is not valid JSON or it does not fulfill `options`, in which case no example
+    /// is added.
+    pub fn add_json_with_options(
+        &mut self,
+        json: &[u8],
+        options: &ParseOptions,
+    ) -> Result<(), Error> {
+        let mut visitor = DocumentVisitor {
+            data_type: InferredType::Null,
+            stack: take(&mut self.stack),
+        };
+        let result = visit_with_options(json, options, &mut visitor);
+        // keep the allocation of the stack for the next document
+        self.stack = visitor.stack;
+        self.stack.clear();
+        result?;
+        self.add_type(visitor.data_type);
+        Ok(())
+    }
+
+    fn add_type(&mut self, data_type: InferredType) {
+        self.data_type = take(&mut self.data_type).merge(data_type);
+    }
+
+    /// Returns the type of all examples added so far
+    pub fn data_type(&self) -> &InferredType {
+        &self.data_type
+    }
+
+    /// Returns the type of all examples
+    pub fn finish(self) -> InferredType {
+        self.data_type
+    }
+}
+
+/// A [`Visitor`] that infers the type of one document
+struct DocumentVisitor {
+    data_type: InferredType,
+    stack: Vec<Partial>,
+}
+
+impl DocumentVisitor {
+    fn add(&mut self, data_type: InferredType) {
+        match self.stack.last_mut() {
+            None => self.data_type = data_type,
+            Some(Partial::List(item)) => item.merge(InferredField::new(String::new(), data_type)),
+            Some(Partial::Struct(fields, key)) => {
+                add_field(fields, InferredField::new(take(key), data_type))
+            }
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for DocumentVisitor {
+    fn visit_null(&mut self) -> Result<(), ErrorKind> {
+        self.add(InferredType::Null);
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, _: bool) -> Result<(), ErrorKind> {
+        self.add(InferredType::Boolean);
+        Ok(())
+    }
+
+    fn visit_number(&mut self, value: Number<'a>) -> Result<(), ErrorKind> {
+        self.add(match value {
+            Number::Integer(..) => InferredType::Integer,
+            Number::Float(..) => InferredType::Float,
+        });
+        Ok(())
+    }
+
+    fn visit_str(&mut self, _: Cow<'a, str>) -> Result<(), ErrorKind> {
+        self.add(InferredType::String);
+        Ok(())
+    }
+
+    fn begin_object(&mut self) -> Result<(), ErrorKind> {
+        self.stack.push(Partial::Struct(vec![], String::new()));
+        Ok(())
+    }
+
+    fn key(&mut self, key: Cow<'a, str>) -> Result<(), ErrorKind> {
+        if let Some(Partial::Struct(_, current)) = self.stack.last_mut() {
+            *current = key.into_owned();
+        }
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), ErrorKind> {
+        if let Some(Partial::Struct(fields, _)) = self.stack.pop() {
+            self.add(InferredType::Struct(fields));
+        }
+        Ok(())
+    }
+
+    fn begin_array(&mut self) -> Result<(), ErrorKind> {
+        self.stack.push(Partial::List(InferredField::item()));
+        Ok(())
+    }
+
+    fn end_array(&mut self) -> Result<(), ErrorKind> {
+        if let Some(Partial::List(item)) = self.stack.pop() {
+            self.add(InferredType::List(Box::new(item)));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the type inferred from `values`. See [`Inference`].
+pub fn infer<'b, 'a: 'b>(values: impl IntoIterator<Item = &'b Value<'a>>) -> InferredType {
+    let mut inference = Inference::new();
+    values
+        .into_iter()
+        .for_each(|value| inference.add_value(value));
+    inference.finish()
+}
+
+/// Returns the type inferred from the lines of newline-delimited JSON, skipping blank lines,
+/// without building [`Value`]s. See [`Inference`].
+/// # Errors
+/// If a line is not valid JSON. The error contains the byte offset in the whole input.
+pub fn infer_ndjson(json: &[u8]) -> Result<InferredType, Error> {
+    infer_ndjson_with_options(json, &ParseOptions::default())
+}
+
+/// Returns the type inferred from the lines of newline-delimited JSON, parsed according to
+/// `options`. See [`infer_ndjson`].
+/// # Errors
+/// If a line is not valid JSON or it does not fulfill `options`.
+pub fn infer_ndjson_with_options(
+    json: &[u8],
+    options: &ParseOptions,
+) -> Result<InferredType, Error> {
+    let mut inference = Inference::new();
+    for_each_line(json, |line| inference.add_json_with_options(line, options))?;
+    Ok(inference.finish())
+}
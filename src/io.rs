@@ -0,0 +1,36 @@
+use alloc::vec::Vec;
+use std::io::{self, Read};
+
+use super::error::*;
+use super::options::ParseOptions;
+use super::parser::{parse_document_in, Owned, Value};
+
+/// Reads `reader` to its end and parses it into a [`Value`] that owns its data.
+/// # Errors
+/// If reading fails, or an error of kind [`io::ErrorKind::InvalidData`] wrapping an [`Error`]
+/// if the input is not valid JSON.
+pub fn from_reader<R: Read>(reader: R) -> io::Result<Value<'static>> {
+    from_reader_with_options(reader, &ParseOptions::default())
+}
+
+/// Reads `reader` to its end and parses it into a [`Value`] that owns its data,
+/// according to `options`. Strings and numbers are copied out of the read bytes while
+/// parsing.
+/// # Errors
+/// If reading fails, or an error of kind [`io::ErrorKind::InvalidData`] wrapping an [`Error`]
+/// if the input is not valid JSON or it does not fulfill `options`.
+pub fn from_reader_with_options<R: Read>(
+    mut reader: R,
+    options: &ParseOptions,
+) -> io::Result<Value<'static>> {
+    let mut json = Vec::new();
+    reader.read_to_end(&mut json)?;
+    let mut values = json.as_slice();
+    parse_document_in::<Owned>(&mut values, options).map_err(|e| e.locate(&json, values).into())
+}
+
+impl From<Error> for io::Error {
+    fn from(error: Error) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, error)
+    }
+}
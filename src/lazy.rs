@@ -0,0 +1,286 @@
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use core::ops::Range;
+
+use super::error::*;
+use super::options::{DuplicateKeys, ParseOptions};
+use super::parser::{current_token, parse_value, Value};
+use super::relaxed::{is_trailing_comma, parse_key, skip_ignored};
+use super::skip::{skip_nested_value, skip_validated_value};
+
+/// A validated JSON value whose arrays and objects are only parsed when accessed,
+/// returned by [`parse_lazy`].
+///
+/// Accessing a value skips its siblings without validating them again, and
+/// [`LazyValue::to_value`] only parses the value itself, which is useful to read a few fields
+/// out of a wide document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LazyValue<'a> {
+    json: &'a [u8],
+    span: Range<usize>,
+    options: ParseOptions,
+    depth: usize,
+}
+
+impl<'a> LazyValue<'a> {
+    /// The unparsed JSON of this value
+    pub fn as_bytes(&self) -> &'a [u8] {
+        &self.json[self.span.clone()]
+    }
+
+    /// The byte span of this value in the input of [`parse_lazy`]
+    pub fn span(&self) -> Range<usize> {
+        self.span.clone()
+    }
+
+    /// Parses this value into a [`Value`]
+    /// # Errors
+    /// With [`DuplicateKeys::Error`], if a key of an object in this value is repeated. Never
+    /// otherwise for values returned by this crate, since they were already validated.
+    pub fn to_value(&self) -> Result<Value<'a>, Error> {
+        let mut values = self.remaining();
+        parse_value(&mut values, &self.options, self.depth).map_err(|e| e.locate(self.json, values))
+    }
+
+    /// Returns the value of `key` if this is an object that contains it.
+    /// The first value wins when the key is repeated with [`DuplicateKeys::FirstWins`]
+    /// and the last one otherwise.
+    /// # Errors
+    /// With [`DuplicateKeys::Error`], if a key of this object is repeated. Never otherwise for
+    /// values returned by this crate, since they were already validated.
+    pub fn get(&self, key: &str) -> Result<Option<LazyValue<'a>>, Error> {
+        let first_wins = self.options.duplicate_keys == DuplicateKeys::FirstWins;
+        let mut result = None;
+        if let Some(iter) = self.iter_object() {
+            for item in iter {
+                let (k, v) = item?;
+                if k == key {
+                    result = Some(v);
+                    if first_wins {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Returns the item at `index` if this is an array with more than `index` items.
+    /// # Errors
+    /// Never for values returned by this crate, since they were already validated and the keys
+    /// of the items are only checked when they are accessed.
+    pub fn index(&self, index: usize) -> Result<Option<LazyValue<'a>>, Error> {
+        self.iter_array()
+            .and_then(|mut iter| iter.nth(index))
+            .transpose()
+    }
+
+    /// Returns an iterator over the items of this value if it is an array
+    pub fn iter_array(&self) -> Option<LazyArrayIter<'a>> {
+        (self.as_bytes()[0] == b'[').then(|| LazyArrayIter(self.items()))
+    }
+
+    /// Returns an iterator over the keys and values of this value if it is an object.
+    /// With [`DuplicateKeys::Error`], it errors at the first repeated key.
+    pub fn iter_object(&self) -> Option<LazyObjectIter<'a>> {
+        (self.as_bytes()[0] == b'{').then(|| LazyObjectIter {
+            keys: (self.options.duplicate_keys == DuplicateKeys::Error).then(BTreeSet::new),
+            items: self.items(),
+        })
+    }
+
+    /// The input starting at this value
+    #[inline]
+    fn remaining(&self) -> &'a [u8] {
+        &self.json[self.span.start..]
+    }
+
+    #[inline]
+    fn items(&self) -> Items<'a> {
+        // skip the opening `[` or `{`
+        let remaining = &self.remaining()[1..];
+        Items::new(
+            self.json,
+            remaining,
+            self.options.clone(),
+            self.depth + 1,
+            true,
+        )
+    }
+}
+
+/// State shared by [`LazyArrayIter`] and [`LazyObjectIter`]
+#[derive(Debug, Clone)]
+struct Items<'a> {
+    json: &'a [u8],
+    remaining: &'a [u8],
+    options: ParseOptions,
+    // the depth of the items
+    depth: usize,
+    // whether the items were already validated and can be skipped without validating them
+    validated: bool,
+    first: bool,
+    finished: bool,
+}
+
+impl<'a> Items<'a> {
+    fn new(
+        json: &'a [u8],
+        remaining: &'a [u8],
+        options: ParseOptions,
+        depth: usize,
+        validated: bool,
+    ) -> Self {
+        Self {
+            json,
+            remaining,
+            options,
+            depth,
+            validated,
+            first: true,
+            finished: false,
+        }
+    }
+
+    /// Consumes the separator before the next item, returning whether there is one
+    fn next_item(&mut self, end: u8) -> Result<bool, ErrorKind> {
+        skip_ignored(&mut self.remaining, &self.options)?;
+        let token = current_token(self.remaining)?;
+        if token == end {
+            return Ok(false);
+        }
+        if !self.first {
+            if token != b',' {
+                return Err(ErrorKind::MissingComma(token));
+            }
+            self.remaining = &self.remaining[1..];
+            if is_trailing_comma(&mut self.remaining, end, &self.options)? {
+                return Ok(false);
+            }
+        }
+        self.first = false;
+        skip_ignored(&mut self.remaining, &self.options)?;
+        Ok(true)
+    }
+
+    fn next_value(&mut self) -> Result<LazyValue<'a>, ErrorKind> {
+        skip_ignored(&mut self.remaining, &self.options)?;
+        let start = self.json.len() - self.remaining.len();
+        if self.validated {
+            skip_validated_value(&mut self.remaining, &self.options);
+        } else {
+            skip_nested_value(&mut self.remaining, &self.options, self.depth)?;
+        }
+        Ok(LazyValue {
+            json: self.json,
+            span: start..self.json.len() - self.remaining.len(),
+            options: self.options.clone(),
+            depth: self.depth,
+        })
+    }
+
+    fn next_key(&mut self) -> Result<Cow<'a, str>, ErrorKind> {
+        let key = parse_key(&mut self.remaining, &self.options)?;
+
+        skip_ignored(&mut self.remaining, &self.options)?;
+        let token = current_token(self.remaining)?;
+        if token != b':' {
+            return Err(ErrorKind::InvalidToken(token));
+        };
+        self.remaining = &self.remaining[1..];
+        Ok(key)
+    }
+
+    /// Returns the next item by calling `next` after the separator, stopping after the first error
+    fn next<T>(
+        &mut self,
+        end: u8,
+        next: impl FnOnce(&mut Self) -> Result<T, ErrorKind>,
+    ) -> Option<Result<T, Error>> {
+        if self.finished {
+            return None;
+        }
+        let result = self
+            .next_item(end)
+            .and_then(|has_item| has_item.then(|| next(self)).transpose());
+        match result {
+            Ok(Some(item)) => Some(Ok(item)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e.locate(self.json, self.remaining)))
+            }
+        }
+    }
+}
+
+/// Iterator over the items of a lazy array, returned by [`LazyValue::iter_array`]
+#[derive(Debug, Clone)]
+pub struct LazyArrayIter<'a>(Items<'a>);
+
+impl<'a> Iterator for LazyArrayIter<'a> {
+    type Item = Result<LazyValue<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next(b']', Items::next_value)
+    }
+}
+
+/// Iterator over the keys and values of a lazy object, returned by [`LazyValue::iter_object`]
+#[derive(Debug, Clone)]
+pub struct LazyObjectIter<'a> {
+    items: Items<'a>,
+    // the keys so far, tracked with `DuplicateKeys::Error`
+    keys: Option<BTreeSet<Cow<'a, str>>>,
+}
+
+impl<'a> Iterator for LazyObjectIter<'a> {
+    type Item = Result<(Cow<'a, str>, LazyValue<'a>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let keys = &mut self.keys;
+        self.items.next(b'}', |items| {
+            let start = items.remaining;
+            let key = items.next_key()?;
+            if let Some(keys) = keys {
+                if !keys.insert(key.clone()) {
+                    // the error is located at the repeated key
+                    items.remaining = start;
+                    return Err(ErrorKind::DuplicateKey(key.into_owned()));
+                }
+            }
+            Ok((key, items.next_value()?))
+        })
+    }
+}
+
+/// Validates JSON-compliant bytes into a [`LazyValue`], without allocating.
+/// # Errors
+/// If and only if `json` is not valid JSON, with the same error [`parse`](crate::parse) returns.
+pub fn parse_lazy(json: &[u8]) -> Result<LazyValue<'_>, Error> {
+    parse_lazy_with_options(json, &ParseOptions::default())
+}
+
+/// Validates JSON-compliant bytes into a [`LazyValue`] according to `options`, without allocating.
+/// # Errors
+/// If `json` is not valid JSON or it does not fulfill `options`.
+pub fn parse_lazy_with_options<'a>(
+    json: &'a [u8],
+    options: &ParseOptions,
+) -> Result<LazyValue<'a>, Error> {
+    let mut items = Items::new(json, json, options.clone(), 0, false);
+    items
+        .next_value()
+        .and_then(|value| {
+            skip_ignored(&mut items.remaining, &items.options)?;
+            if items.remaining.is_empty() {
+                Ok(value)
+            } else {
+                Err(ErrorKind::InvalidEOF)
+            }
+        })
+        .map_err(|e| e.locate(json, items.remaining))
+}
@@ -1,18 +1,71 @@
 //! Library to parse JSON
 #![deny(missing_docs)]
 #![no_std]
-#![forbid(unsafe_code)]
+#![cfg_attr(not(feature = "simd"), forbid(unsafe_code))]
+#![cfg_attr(feature = "simd", deny(unsafe_code))]
 #[macro_use]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 mod array;
+#[cfg(feature = "arrow")]
+mod arrow;
 mod boolean;
+#[cfg(feature = "serde")]
+mod de;
 mod error;
+mod incremental;
+mod infer;
+#[cfg(feature = "std")]
+mod io;
+mod lazy;
+mod ndjson;
 mod null;
 mod number;
 mod object;
+mod options;
 mod parser;
+mod pointer;
+mod projection;
+mod reader;
+mod relaxed;
+mod schema;
+mod skip;
+mod stream;
 mod string;
+mod structural;
+mod tape;
+mod visitor;
+mod writer;
 
+#[cfg(feature = "arrow")]
+pub use arrow::{
+    parse_ndjson_record_batches, parse_ndjson_record_batches_with_options, parse_record_batches,
+    parse_record_batches_with_options, ArrowOptions,
+};
+#[cfg(feature = "serde")]
+pub use de::{from_slice, from_slice_with_options, Deserializer};
 pub use error::*;
-pub use parser::{parse, Number, Object, Value};
+pub use incremental::{IncrementalReader, Status};
+pub use infer::{
+    infer, infer_ndjson, infer_ndjson_with_options, Inference, InferredField, InferredType,
+};
+#[cfg(feature = "std")]
+pub use io::{from_reader, from_reader_with_options};
+pub use lazy::{parse_lazy, parse_lazy_with_options, LazyArrayIter, LazyObjectIter, LazyValue};
+pub use ndjson::{parse_ndjson, parse_ndjson_with_options, NdjsonIter, NdjsonOptions};
+pub use options::{DuplicateKeys, ParseOptions};
+pub use parser::{parse, parse_with_options, Number, Object, Value};
+pub use pointer::{parse_pointer, parse_pointer_with_options};
+pub use projection::{parse_projected, parse_projected_with_options, Projection};
+pub use reader::{Event, Reader};
+pub use schema::{JsonSchema, SchemaError, ValidationError};
+pub use skip::{skip_value, skip_value_with_options, validate, validate_with_options};
+pub use stream::{parse_stream, parse_stream_with_options, StreamIter};
+pub use tape::{
+    parse_tape, parse_tape_with_options, Tape, TapeArrayIter, TapeEntries, TapeEntry,
+    TapeObjectIter, TapeValue,
+};
+pub use visitor::{visit, visit_with_options, Visitor};
+pub use writer::{to_string, to_string_pretty, to_vec, write, write_pretty};
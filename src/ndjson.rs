@@ -0,0 +1,135 @@
+use super::error::*;
+use super::options::ParseOptions;
+use super::parser::{parse_document, skip_unused, Value};
+
+/// Options to parse newline-delimited JSON, built from [`NdjsonOptions::default`] and its
+/// `with_*` methods
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct NdjsonOptions {
+    /// Options used to parse each line
+    pub parse: ParseOptions,
+    /// Whether lines containing only whitespace are skipped instead of yielding an error
+    pub skip_blank_lines: bool,
+    /// Whether to continue to the next line after a line fails to parse instead of stopping
+    pub continue_on_error: bool,
+}
+
+impl NdjsonOptions {
+    /// Returns these options with [`NdjsonOptions::parse`] set to `parse`
+    pub fn with_parse(mut self, parse: ParseOptions) -> Self {
+        self.parse = parse;
+        self
+    }
+
+    /// Returns these options with [`NdjsonOptions::skip_blank_lines`] set to `skip_blank_lines`
+    pub fn with_skip_blank_lines(mut self, skip_blank_lines: bool) -> Self {
+        self.skip_blank_lines = skip_blank_lines;
+        self
+    }
+
+    /// Returns these options with [`NdjsonOptions::continue_on_error`] set to
+    /// `continue_on_error`
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+}
+
+/// Iterator over the values of newline-delimited JSON (also known as JSON Lines),
+/// returned by [`parse_ndjson`].
+///
+/// Errors contain the byte offset in the whole input; [`NdjsonIter::line`] returns the line
+/// number of the last value or error returned.
+#[derive(Debug, Clone)]
+pub struct NdjsonIter<'a> {
+    json: &'a [u8],
+    remaining: &'a [u8],
+    line: usize,
+    options: NdjsonOptions,
+    finished: bool,
+}
+
+impl<'a> NdjsonIter<'a> {
+    /// The line number (starting at 1) of the last item returned by this iterator.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl<'a> Iterator for NdjsonIter<'a> {
+    type Item = Result<Value<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.finished || self.remaining.is_empty() {
+                return None;
+            }
+            let offset = self.json.len() - self.remaining.len();
+            let line = next_line(&mut self.remaining);
+            self.line += 1;
+
+            let mut values = line;
+            skip_unused(&mut values);
+            if values.is_empty() && self.options.skip_blank_lines {
+                continue;
+            }
+
+            let result = parse_document(&mut values, &self.options.parse)
+                .map_err(|e| Error::new(e, offset + line.len() - values.len()));
+            if result.is_err() && !self.options.continue_on_error {
+                self.finished = true;
+            }
+            return Some(result);
+        }
+    }
+}
+
+/// Parses newline-delimited JSON, yielding one [`Value`] per line.
+/// The iterator stops after the first line that fails to parse.
+pub fn parse_ndjson(json: &[u8]) -> NdjsonIter<'_> {
+    parse_ndjson_with_options(json, NdjsonOptions::default())
+}
+
+/// Parses newline-delimited JSON according to `options`, yielding one [`Value`] per line.
+pub fn parse_ndjson_with_options(json: &[u8], options: NdjsonOptions) -> NdjsonIter<'_> {
+    NdjsonIter {
+        json,
+        remaining: json,
+        line: 0,
+        options,
+        finished: false,
+    }
+}
+
+/// Consumes and returns the next line of `remaining`, without its `\n`
+#[inline]
+fn next_line<'a>(remaining: &mut &'a [u8]) -> &'a [u8] {
+    match remaining.iter().position(|x| *x == b'\n') {
+        Some(end) => {
+            let line = &remaining[..end];
+            *remaining = &remaining[end + 1..];
+            line
+        }
+        None => core::mem::take(remaining),
+    }
+}
+
+/// Calls `f` with each line of `json` that is not blank, relocating the errors it returns, which
+/// are located in the line, to the whole input
+pub fn for_each_line(
+    json: &[u8],
+    mut f: impl FnMut(&[u8]) -> Result<(), Error>,
+) -> Result<(), Error> {
+    let mut remaining = json;
+    while !remaining.is_empty() {
+        let offset = json.len() - remaining.len();
+        let line = next_line(&mut remaining);
+        let mut values = line;
+        skip_unused(&mut values);
+        if !values.is_empty() {
+            f(line).map_err(|e| Error::new(e.kind().clone(), offset + e.offset()))?;
+        }
+    }
+    Ok(())
+}
@@ -1,8 +1,93 @@
+use alloc::format;
+
 use super::error::*;
 use super::Number;
 
 #[inline]
-pub fn parse_number<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Number<'a>, Error> {
+pub fn parse_number<'a>(values: &mut &'a [u8]) -> Result<Number<'a>, ErrorKind> {
+    let (mantissa, exponent, is_float) = lex_number(values, next_state)?;
+    Ok(if is_float {
+        Number::Float(mantissa.into(), exponent.into())
+    } else {
+        Number::Integer(mantissa.into(), exponent.into())
+    })
+}
+
+/// Parses a number of the relaxed syntax, i.e. also with a leading `+` or `.`, hexadecimal
+/// digits, `Infinity` or `NaN`. Numbers are normalized to JSON except for `Infinity` and `NaN`.
+pub fn parse_relaxed_number<'a>(values: &mut &'a [u8]) -> Result<Number<'a>, ErrorKind> {
+    let number = *values;
+    let (sign, unsigned) = split_sign(number);
+    for word in [&b"Infinity"[..], b"NaN"] {
+        if unsigned.starts_with(word) {
+            *values = &unsigned[word.len()..];
+            let mantissa = if sign == b"+" {
+                &unsigned[..word.len()]
+            } else {
+                &number[..sign.len() + word.len()]
+            };
+            return Ok(Number::Float(mantissa.into(), b"".into()));
+        }
+    }
+    match unsigned.first() {
+        Some(b'0'..=b'9' | b'.') => {}
+        Some(token) => {
+            *values = unsigned;
+            return Err(ErrorKind::InvalidToken(*token));
+        }
+        None => {
+            *values = unsigned;
+            return Err(ErrorKind::InvalidEOF);
+        }
+    }
+
+    let (mantissa, exponent, is_float) = lex_number(values, next_relaxed_state)?;
+    let (sign, unsigned) = split_sign(mantissa);
+    // a leading `+` is dropped
+    let (sign, mantissa) = if sign == b"+" {
+        (&[][..], unsigned)
+    } else {
+        (sign, mantissa)
+    };
+    Ok(match unsigned {
+        [b'0', b'x' | b'X', digits @ ..] => {
+            let value = parse_hex(digits).ok_or_else(|| {
+                *values = number;
+                ErrorKind::NumberOverflow
+            })?;
+            let mut decimal = sign.to_vec();
+            decimal.extend_from_slice(format!("{value}").as_bytes());
+            Number::Integer(decimal.into(), exponent.into())
+        }
+        [b'.', ..] => Number::Float([sign, b"0", unsigned].concat().into(), exponent.into()),
+        _ if is_float => Number::Float(mantissa.into(), exponent.into()),
+        _ => Number::Integer(mantissa.into(), exponent.into()),
+    })
+}
+
+/// Splits a leading `-` or `+` from `number`
+#[inline]
+fn split_sign(number: &[u8]) -> (&[u8], &[u8]) {
+    match number.first() {
+        Some(b'-' | b'+') => number.split_at(1),
+        _ => number.split_at(0),
+    }
+}
+
+fn parse_hex(digits: &[u8]) -> Option<u128> {
+    digits.iter().try_fold(0u128, |acc, x| {
+        let digit = (*x as char).to_digit(16)? as u128;
+        acc.checked_mul(16)?.checked_add(digit)
+    })
+}
+
+/// Lexes a number, transitioning between states with `next`, returning its mantissa, its
+/// exponent and whether it is a float
+#[inline]
+fn lex_number<'a>(
+    values: &mut &'a [u8],
+    next: fn(u8, State) -> Result<State, ErrorKind>,
+) -> Result<(&'a [u8], &'a [u8], bool), ErrorKind> {
     let number = *values;
 
     let mut is_float = false;
@@ -10,8 +95,8 @@ pub fn parse_number<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Number<'a>, Erro
     let mut length = 0;
 
     let mut prev_state = State::Start;
-    let byte = values.get(0).ok_or(Error::InvalidEOF)?;
-    let mut state = next_state(*byte, prev_state)?;
+    let byte = values.first().ok_or(ErrorKind::InvalidEOF)?;
+    let mut state = next(*byte, prev_state)?;
 
     loop {
         if matches!(state, State::FractionStart | State::ExponentSignedNegative) {
@@ -24,30 +109,41 @@ pub fn parse_number<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Number<'a>, Erro
 
         if matches!(
             state,
-            State::Signed | State::Zero | State::Nonzero | State::FractionStart | State::Fraction
+            State::Signed
+                | State::Zero
+                | State::Nonzero
+                | State::FractionStart
+                | State::Fraction
+                | State::HexStart
+                | State::Hex
         ) {
             number_end += 1;
         }
 
-        *values = values.get(1..).ok_or(Error::InvalidEOF)?;
+        *values = values.get(1..).ok_or(ErrorKind::InvalidEOF)?;
 
         if values.is_empty() {
             break;
         }
 
-        let byte = values.get(0).ok_or(Error::InvalidEOF)?;
+        let byte = values.first().ok_or(ErrorKind::InvalidEOF)?;
 
-        state = next_state(*byte, state)?;
+        state = next(*byte, state)?;
 
         if state == State::Finished {
             break;
         }
     }
     match prev_state {
-        State::FractionStart => Err(Error::NumberWithEmptyFraction),
+        State::FractionStart => Err(ErrorKind::NumberWithEmptyFraction),
         State::ExponentStart | State::ExponentSignedPositive | State::ExponentSignedNegative => {
-            Err(Error::NumberWithEmptyExponent)
+            Err(ErrorKind::NumberWithEmptyExponent)
         }
+        State::HexStart => Err(ErrorKind::InvalidEOF),
+        // a sign without digits, e.g. `[-]`
+        State::Signed => Err(values
+            .first()
+            .map_or(ErrorKind::InvalidEOF, |byte| ErrorKind::InvalidToken(*byte))),
         _ => {
             let number = &number[..length];
             let exponent = if number_end == number.len() {
@@ -55,18 +151,13 @@ pub fn parse_number<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Number<'a>, Erro
             } else {
                 &number[number_end + 1..]
             };
-            let number = &number[..number_end];
-            Ok(if is_float {
-                Number::Float(number, exponent)
-            } else {
-                Number::Integer(number, exponent)
-            })
+            Ok((&number[..number_end], exponent, is_float))
         }
     }
 }
 
 /// The state of the string lexer
-#[derive(Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum State {
     Finished, // when it is done
     Start,
@@ -79,20 +170,23 @@ pub enum State {
     ExponentSignedPositive,
     ExponentSignedNegative,
     Exponent,
+    // the relaxed syntax's hexadecimal numbers, after `0x` and after a digit
+    HexStart,
+    Hex,
 }
 
 /// The transition state of the lexer
 #[inline]
-fn next_state(byte: u8, state: State) -> Result<State, Error> {
+pub fn next_state(byte: u8, state: State) -> Result<State, ErrorKind> {
     Ok(match (byte, &state) {
         (b'-', State::Start) => State::Signed,
         (b'0', State::Start | State::Signed) => State::Zero,
         (b'1'..=b'9', State::Start | State::Signed) => State::Nonzero,
 
-        (b'0'..=b'9', State::Zero) => return Err(Error::NumberWithLeadingZero),
+        (b'0'..=b'9', State::Zero) => return Err(ErrorKind::NumberWithLeadingZero),
 
         (b'.', State::Zero | State::Nonzero) => State::FractionStart,
-        (b'e' | b'E', State::FractionStart) => return Err(Error::NumberWithEmptyFraction),
+        (b'e' | b'E', State::FractionStart) => return Err(ErrorKind::NumberWithEmptyFraction),
         (b'e' | b'E', State::Zero | State::Nonzero | State::Fraction) => State::ExponentStart,
 
         (b'0'..=b'9', State::Nonzero) => State::Nonzero,
@@ -112,3 +206,283 @@ fn next_state(byte: u8, state: State) -> Result<State, Error> {
         (_, _) => State::Finished,
     })
 }
+
+/// The transition state of the lexer of the relaxed syntax
+#[inline]
+pub fn next_relaxed_state(byte: u8, state: State) -> Result<State, ErrorKind> {
+    Ok(match (byte, &state) {
+        (b'+', State::Start) => State::Signed,
+        (b'.', State::Start | State::Signed) => State::FractionStart,
+        (b'x' | b'X', State::Zero) => State::HexStart,
+        (b'0'..=b'9' | b'a'..=b'f' | b'A'..=b'F', State::HexStart | State::Hex) => State::Hex,
+        (_, State::HexStart) => return Err(ErrorKind::InvalidHex(byte)),
+        (_, State::Hex) => State::Finished,
+        _ => return next_state(byte, state),
+    })
+}
+
+// exponents beyond this saturate; they can't be represented by any supported type anyway
+const MAX_EXPONENT: i64 = 1 << 40;
+
+// The correctly rounded `f64` (and `f32`) of a decimal is determined by its first 767
+// significant digits and whether any of the remaining digits is non-zero.
+const MAX_DIGITS: usize = 768;
+
+/// The decimal representation of a [`Number`], `(-1)^negative * digits * 10^exponent`,
+/// where `digits` are the integer digits followed by the fraction digits.
+struct Decimal<'a> {
+    negative: bool,
+    integer: &'a [u8],
+    fraction: &'a [u8],
+    exponent: i64,
+}
+
+impl<'a> Decimal<'a> {
+    fn new(mantissa: &'a [u8], exponent: &'a [u8]) -> Self {
+        let (negative, mantissa) = match mantissa.split_first() {
+            Some((b'-', rest)) => (true, rest),
+            _ => (false, mantissa),
+        };
+        let (integer, fraction) = match mantissa.iter().position(|x| *x == b'.') {
+            Some(dot) => (&mantissa[..dot], &mantissa[dot + 1..]),
+            None => (mantissa, &[][..]),
+        };
+
+        let (exponent_negative, exponent) = match exponent.split_first() {
+            Some((b'-', rest)) => (true, rest),
+            Some((b'+', rest)) => (false, rest),
+            _ => (false, exponent),
+        };
+        let exponent = exponent.iter().fold(0i64, |acc, x| {
+            (acc * 10 + (x - b'0') as i64).min(MAX_EXPONENT)
+        });
+        let exponent = if exponent_negative {
+            -exponent
+        } else {
+            exponent
+        };
+
+        Self {
+            negative,
+            integer,
+            fraction,
+            exponent: exponent - fraction.len() as i64,
+        }
+    }
+
+    /// The significant digits, i.e. without leading zeros
+    fn digits(&self) -> impl Iterator<Item = u8> + Clone + 'a {
+        self.integer
+            .iter()
+            .chain(self.fraction.iter())
+            .copied()
+            .skip_while(|x| *x == b'0')
+    }
+
+    /// Returns the sign and magnitude of this decimal if it is an integer that fits in a `u128`
+    fn to_integer(&self) -> Result<(bool, u128), ErrorKind> {
+        let digits = self.digits();
+        let mut length = digits.clone().count();
+        let trailing_zeros = digits
+            .clone()
+            .fold(0, |acc, x| if x == b'0' { acc + 1 } else { 0 });
+
+        let mut exponent = self.exponent;
+        if exponent < 0 {
+            // e.g. 1.50 == 15 * 10^-1
+            let shift = trailing_zeros.min(exponent.unsigned_abs() as usize);
+            length -= shift;
+            exponent += shift as i64;
+        }
+        if length == 0 {
+            return Ok((self.negative, 0));
+        }
+        if exponent < 0 {
+            return Err(ErrorKind::NumberPrecisionLoss);
+        }
+
+        let mut value = digits.take(length).try_fold(0u128, |acc, x| {
+            acc.checked_mul(10)?.checked_add((x - b'0') as u128)
+        });
+        for _ in 0..exponent {
+            value = value.and_then(|x| x.checked_mul(10));
+            if value.is_none() {
+                break;
+            }
+        }
+        value
+            .map(|x| (self.negative, x))
+            .ok_or(ErrorKind::NumberOverflow)
+    }
+
+    /// Writes this decimal as a string of at most [`MAX_DIGITS`] + 1 significant digits that
+    /// rounds to the same float, returning it and whether it is non-zero.
+    fn write<'b>(&self, buffer: &'b mut [u8; MAX_DIGITS + 32]) -> (&'b str, bool) {
+        let mut length = 0;
+        let mut push = |byte: u8| {
+            buffer[length] = byte;
+            length += 1;
+        };
+
+        if self.negative {
+            push(b'-');
+        }
+        let mut digits = self.digits();
+        let mut exponent = self.exponent;
+        let mut count = 0;
+        for digit in digits.by_ref().take(MAX_DIGITS) {
+            push(digit);
+            count += 1;
+        }
+        let remaining = digits.clone().count() as i64;
+        if count == 0 {
+            push(b'0');
+        } else if digits.any(|x| x != b'0') {
+            // a sticky digit so that the truncated value rounds correctly
+            push(b'1');
+            exponent += remaining - 1;
+        } else {
+            exponent += remaining;
+        }
+
+        push(b'e');
+        if exponent < 0 {
+            push(b'-');
+        }
+        let exponent = exponent.unsigned_abs();
+        let mut divisor = 1;
+        while divisor * 10 <= exponent {
+            divisor *= 10;
+        }
+        while divisor > 0 {
+            push(b'0' + ((exponent / divisor) % 10) as u8);
+            divisor /= 10;
+        }
+
+        // the buffer only contains ASCII
+        (core::str::from_utf8(&buffer[..length]).unwrap(), count > 0)
+    }
+}
+
+macro_rules! to_float {
+    ($self:ident, $type:ty) => {{
+        if let Some(value) = $self.non_finite() {
+            return Ok(value as $type);
+        }
+        let mut buffer = [0; MAX_DIGITS + 32];
+        let (string, non_zero) = $self.decimal().write(&mut buffer);
+        // `string` is always a valid float
+        let value = string.parse::<$type>().unwrap();
+        if value.is_infinite() {
+            Err(ErrorKind::NumberOverflow)
+        } else if value == 0.0 && non_zero {
+            Err(ErrorKind::NumberPrecisionLoss)
+        } else {
+            Ok(value)
+        }
+    }};
+}
+
+impl<'a> Number<'a> {
+    /// Returns a [`Number`] that owns its data, copying data borrowed from the input.
+    pub fn into_owned(self) -> Number<'static> {
+        match self {
+            Number::Float(mantissa, exponent) => {
+                Number::Float(mantissa.into_owned().into(), exponent.into_owned().into())
+            }
+            Number::Integer(mantissa, exponent) => {
+                Number::Integer(mantissa.into_owned().into(), exponent.into_owned().into())
+            }
+        }
+    }
+
+    #[inline]
+    fn decimal(&self) -> Decimal<'_> {
+        match self {
+            Number::Float(mantissa, exponent) | Number::Integer(mantissa, exponent) => {
+                Decimal::new(mantissa, exponent)
+            }
+        }
+    }
+
+    /// Returns the value of `Infinity`, `-Infinity` or `NaN` of the relaxed syntax
+    pub(crate) fn non_finite(&self) -> Option<f64> {
+        match self {
+            Number::Float(mantissa, _) => match mantissa.as_ref() {
+                b"Infinity" => Some(f64::INFINITY),
+                b"-Infinity" => Some(f64::NEG_INFINITY),
+                b"NaN" | b"-NaN" => Some(f64::NAN),
+                _ => None,
+            },
+            Number::Integer(..) => None,
+        }
+    }
+
+    /// Returns the sign and magnitude of this number if it is an integer that fits in a `u128`
+    fn to_integer(&self) -> Result<(bool, u128), ErrorKind> {
+        match self.non_finite() {
+            Some(value) if value.is_nan() => Err(ErrorKind::NumberPrecisionLoss),
+            Some(_) => Err(ErrorKind::NumberOverflow),
+            None => self.decimal().to_integer(),
+        }
+    }
+
+    /// Converts this number to `i64`.
+    /// # Errors
+    /// [`ErrorKind::NumberOverflow`] if the number is out of range and
+    /// [`ErrorKind::NumberPrecisionLoss`] if it has a non-zero fractional part.
+    pub fn to_i64(&self) -> Result<i64, ErrorKind> {
+        i64::try_from(self.to_i128()?).map_err(|_| ErrorKind::NumberOverflow)
+    }
+
+    /// Converts this number to `u64`.
+    /// # Errors
+    /// [`ErrorKind::NumberOverflow`] if the number is out of range (including negative numbers)
+    /// and [`ErrorKind::NumberPrecisionLoss`] if it has a non-zero fractional part.
+    pub fn to_u64(&self) -> Result<u64, ErrorKind> {
+        u64::try_from(self.to_u128()?).map_err(|_| ErrorKind::NumberOverflow)
+    }
+
+    /// Converts this number to `i128`.
+    /// # Errors
+    /// [`ErrorKind::NumberOverflow`] if the number is out of range and
+    /// [`ErrorKind::NumberPrecisionLoss`] if it has a non-zero fractional part.
+    pub fn to_i128(&self) -> Result<i128, ErrorKind> {
+        let (negative, magnitude) = self.to_integer()?;
+        if negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+        .ok_or(ErrorKind::NumberOverflow)
+    }
+
+    /// Converts this number to `u128`.
+    /// # Errors
+    /// [`ErrorKind::NumberOverflow`] if the number is out of range (including negative numbers)
+    /// and [`ErrorKind::NumberPrecisionLoss`] if it has a non-zero fractional part.
+    pub fn to_u128(&self) -> Result<u128, ErrorKind> {
+        match self.to_integer()? {
+            (true, magnitude) if magnitude > 0 => Err(ErrorKind::NumberOverflow),
+            (_, magnitude) => Ok(magnitude),
+        }
+    }
+
+    /// Converts this number to the nearest `f64`. `Infinity` and `NaN` of the relaxed syntax
+    /// are converted to their `f64` counterparts.
+    /// # Errors
+    /// [`ErrorKind::NumberOverflow`] if the number is larger than [`f64::MAX`] in magnitude and
+    /// [`ErrorKind::NumberPrecisionLoss`] if it is non-zero but rounds to zero.
+    pub fn to_f64(&self) -> Result<f64, ErrorKind> {
+        to_float!(self, f64)
+    }
+
+    /// Converts this number to the nearest `f32`. `Infinity` and `NaN` of the relaxed syntax
+    /// are converted to their `f32` counterparts.
+    /// # Errors
+    /// [`ErrorKind::NumberOverflow`] if the number is larger than [`f32::MAX`] in magnitude and
+    /// [`ErrorKind::NumberPrecisionLoss`] if it is non-zero but rounds to zero.
+    pub fn to_f32(&self) -> Result<f32, ErrorKind> {
+        to_float!(self, f32)
+    }
+}
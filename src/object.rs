@@ -1,54 +1,114 @@
 use alloc::borrow::Cow;
 
 use crate::{
-    parser::{current_token, parse_value, skip_unused},
-    string::parse_string,
-    Object, Value,
+    parser::{check_depth, current_token, parse_value_in, Storage},
+    relaxed::{is_trailing_comma, parse_key, skip_ignored},
+    DuplicateKeys, Object, ParseOptions, Value,
 };
 
 use super::error::*;
 
 // assumes that `values` contains `{`
-pub fn parse_object<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Object<'a>, Error> {
-    *values = &values[1..];
+pub fn parse_object<'a, 'b, S: Storage<'a, 'b>>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<Value<'b>, ErrorKind> {
+    let policy = options.duplicate_keys;
+    if policy == DuplicateKeys::KeepAll {
+        let mut entries = vec![];
+        parse_entries::<S>(values, options, depth, |key, value| {
+            entries.push((key, value));
+            Ok(())
+        })?;
+        return Ok(Value::MultiObject(entries));
+    }
     let mut items = Object::new();
+    parse_entries::<S>(values, options, depth, |key, value| {
+        insert(&mut items, key, value, policy)
+    })?;
+    Ok(Value::Object(items))
+}
+
+/// Parses the entries of an object, passing each key and value to `entry`.
+/// Errors returned by `entry` are located at the key.
+#[inline]
+fn parse_entries<'a, 'b, S: Storage<'a, 'b>>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+    mut entry: impl FnMut(Cow<'b, str>, Value<'b>) -> Result<(), ErrorKind>,
+) -> Result<(), ErrorKind> {
+    check_depth(options, depth)?;
+    *values = &values[1..];
+    let mut is_first = true;
     loop {
-        skip_unused(values);
+        skip_ignored(values, options)?;
         let token = current_token(values)?;
         if token == b'}' {
             *values = &values[1..];
             break;
         };
-        if !items.is_empty() {
+        if !is_first {
             if token != b',' {
-                return Err(Error::MissingComma(values[0]));
+                return Err(ErrorKind::MissingComma(values[0]));
             }
             *values = &values[1..]; // consume ","
-            skip_unused(values);
+            if is_trailing_comma(values, b'}', options)? {
+                break;
+            }
+            skip_ignored(values, options)?;
         }
-        
-        let token = current_token(values)?;
-        if token != b'"' {
-            return Err(Error::InvalidStringToken(token))
+        is_first = false;
+
+        let key_start = *values;
+        let (k, v) = parse_item::<S>(values, options, depth)?;
+        if let Err(error) = entry(k, v) {
+            *values = key_start;
+            return Err(error);
         }
+    }
+    Ok(())
+}
 
-        let (k, v) = parse_item(values)?;
-        items.insert(k.into_owned(), v);
+/// Inserts an entry into `items` according to `policy`, which must not be
+/// [`DuplicateKeys::KeepAll`]
+#[inline]
+pub fn insert<'a>(
+    items: &mut Object<'a>,
+    key: Cow<'a, str>,
+    value: Value<'a>,
+    policy: DuplicateKeys,
+) -> Result<(), ErrorKind> {
+    match policy {
+        DuplicateKeys::FirstWins => {
+            items.entry(key).or_insert(value);
+        }
+        DuplicateKeys::Error if items.contains_key(&key) => {
+            return Err(ErrorKind::DuplicateKey(key.into_owned()))
+        }
+        _ => {
+            items.insert(key, value);
+        }
     }
-    Ok(items)
+    Ok(())
 }
 
 #[inline]
-fn parse_item<'b, 'a>(values: &'b mut &'a [u8]) -> Result<(Cow<'a, str>, Value<'a>), Error> {
-    let key = parse_string(values)?;
+fn parse_item<'a, 'b, S: Storage<'a, 'b>>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<(Cow<'b, str>, Value<'b>), ErrorKind> {
+    let key = S::string(parse_key(values, options)?);
 
-    skip_unused(values);
+    skip_ignored(values, options)?;
     let token = current_token(values)?;
     if token != b':' {
-        return Err(Error::InvalidToken(token));
+        return Err(ErrorKind::InvalidToken(token));
     };
     *values = &values[1..];
 
-    let value = parse_value(values)?;
+    let value = parse_value_in::<S>(values, options, depth + 1)?;
     Ok((key, value))
 }
@@ -0,0 +1,73 @@
+/// Options to parse JSON, built from [`ParseOptions::default`] and its `with_*` methods
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// The maximum number of nested arrays and objects. Deeper inputs fail with
+    /// [`ErrorKind::RecursionLimitExceeded`](crate::ErrorKind::RecursionLimitExceeded)
+    /// instead of overflowing the stack.
+    pub max_depth: usize,
+    /// What to do when a key is repeated in an object.
+    pub duplicate_keys: DuplicateKeys,
+    /// Whether to also accept a relaxed syntax, in the spirit of JSON5, with
+    /// * `//` and `/* */` comments
+    /// * trailing commas in arrays and objects
+    /// * single-quoted strings, where `\'` escapes a single quote
+    /// * keys that are identifiers of ASCII letters, digits, `_` and `$`, e.g. `{a_1: 1}`
+    /// * numbers with a leading `+` or `.`, hexadecimal numbers (e.g. `0x1F`), `Infinity` and `NaN`
+    ///
+    /// Numbers are normalized to JSON, e.g. `.5` to `0.5` and `0x1F` to `31`, except `Infinity`
+    /// and `NaN`. [`IncrementalReader`](crate::IncrementalReader) does not support it and errors
+    /// with [`ErrorKind::UnsupportedOption`](crate::ErrorKind::UnsupportedOption).
+    pub relaxed: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            duplicate_keys: DuplicateKeys::default(),
+            relaxed: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Returns these options with [`ParseOptions::max_depth`] set to `max_depth`
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Returns these options with [`ParseOptions::duplicate_keys`] set to `duplicate_keys`
+    pub fn with_duplicate_keys(mut self, duplicate_keys: DuplicateKeys) -> Self {
+        self.duplicate_keys = duplicate_keys;
+        self
+    }
+
+    /// Returns these options with [`ParseOptions::relaxed`] set to `relaxed`
+    pub fn with_relaxed(mut self, relaxed: bool) -> Self {
+        self.relaxed = relaxed;
+        self
+    }
+}
+
+/// Policy for keys repeated in an object, e.g. `{"a": 1, "a": 2}`.
+///
+/// It applies to functions building objects into [`Value`](crate::Value)s and to objects of a
+/// [`LazyValue`](crate::LazyValue) when they are accessed; functions that skip or stream
+/// objects, such as [`validate`](crate::validate) or [`Reader`](crate::Reader), do not track
+/// keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DuplicateKeys {
+    /// The last value of the key is kept
+    #[default]
+    LastWins,
+    /// The first value of the key is kept
+    FirstWins,
+    /// Parsing fails with [`ErrorKind::DuplicateKey`](crate::ErrorKind::DuplicateKey)
+    /// at the repeated key
+    Error,
+    /// All entries are kept in order: objects are parsed into
+    /// [`Value::MultiObject`](crate::Value::MultiObject)
+    KeepAll,
+}
@@ -1,5 +1,4 @@
 use alloc::borrow::Cow;
-use alloc::string::String;
 use alloc::vec::Vec;
 
 use super::array::parse_array;
@@ -7,24 +6,27 @@ use super::boolean::parse_false;
 use super::boolean::parse_true;
 use super::error::*;
 use super::null::parse_null;
-use super::number::parse_number;
+use super::number::parse_relaxed_number;
 use super::object::parse_object;
-use super::string::parse_string;
+use super::options::ParseOptions;
+use super::relaxed::{parse_any_number, skip_ignored};
+use super::string::{parse_single_quoted, parse_string};
+use super::structural::whitespace_run;
 
 /// Typedef for the inside of an object.
 #[cfg(not(feature = "preserve_order"))]
-pub type Object<'a> = alloc::collections::BTreeMap<String, Value<'a>>;
+pub type Object<'a> = alloc::collections::BTreeMap<Cow<'a, str>, Value<'a>>;
 /// Typedef for the inside of an object.
 #[cfg(feature = "preserve_order")]
-pub type Object<'a> = indexmap::IndexMap<String, Value<'a>>;
+pub type Object<'a> = indexmap::IndexMap<Cow<'a, str>, Value<'a>>;
 
 /// Reference to JSON data.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Number<'a> {
     /// A float (contains exactly 1 period)
-    Float(&'a [u8], &'a [u8]),
+    Float(Cow<'a, [u8]>, Cow<'a, [u8]>),
     /// An integer (contains exactly 0 periods)
-    Integer(&'a [u8], &'a [u8]),
+    Integer(Cow<'a, [u8]>, Cow<'a, [u8]>),
 }
 
 /// Reference to JSON data.
@@ -42,53 +44,175 @@ pub enum Value<'a> {
     Object(Object<'a>),
     /// An array (i.e. items inside squared brackets `[]` separated by comma `,`)
     Array(Vec<Value<'a>>),
+    /// An object whose entries are kept in order, including repeated keys. Only used with
+    /// [`DuplicateKeys::KeepAll`](crate::DuplicateKeys::KeepAll)
+    MultiObject(Vec<(Cow<'a, str>, Value<'a>)>),
+}
+
+impl<'a> Value<'a> {
+    /// Returns a [`Value`] that owns all its data, copying data borrowed from the input.
+    pub fn into_owned(self) -> Value<'static> {
+        match self {
+            Value::Null => Value::Null,
+            Value::String(string) => Value::String(Cow::Owned(string.into_owned())),
+            Value::Number(number) => Value::Number(number.into_owned()),
+            Value::Bool(bool) => Value::Bool(bool),
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| (Cow::Owned(key.into_owned()), value.into_owned()))
+                    .collect(),
+            ),
+            Value::Array(array) => Value::Array(array.into_iter().map(Value::into_owned).collect()),
+            Value::MultiObject(entries) => Value::MultiObject(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (Cow::Owned(key.into_owned()), value.into_owned()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// How the strings and numbers of the input, of lifetime `'a`, are stored in parsed
+/// [`Value`]s of lifetime `'b`
+pub trait Storage<'a, 'b> {
+    fn string(string: Cow<'a, str>) -> Cow<'b, str>;
+    fn number(number: Number<'a>) -> Number<'b>;
+}
+
+/// Strings and numbers are borrowed from the input where possible
+pub struct Borrowed;
+
+impl<'a> Storage<'a, 'a> for Borrowed {
+    #[inline]
+    fn string(string: Cow<'a, str>) -> Cow<'a, str> {
+        string
+    }
+
+    #[inline]
+    fn number(number: Number<'a>) -> Number<'a> {
+        number
+    }
+}
+
+/// Strings and numbers are copied out of the input
+#[cfg(feature = "std")]
+pub struct Owned;
+
+#[cfg(feature = "std")]
+impl<'a> Storage<'a, 'static> for Owned {
+    #[inline]
+    fn string(string: Cow<'a, str>) -> Cow<'static, str> {
+        Cow::Owned(string.into_owned())
+    }
+
+    #[inline]
+    fn number(number: Number<'a>) -> Number<'static> {
+        number.into_owned()
+    }
 }
 
 /// Parses JSON-compliant bytes into [`Value`]
 /// # Errors
-/// If and only if `json` is not valid JSON.
+/// If and only if `json` is not valid JSON. The error contains the byte offset of `json`
+/// where the problem was found.
 /// # Panics
 /// If and only if there is not enough memory to allocate.
-pub fn parse(mut json: &[u8]) -> Result<Value, Error> {
-    let res = parse_value(&mut json)?;
-    skip_unused(&mut json);
-    if json.is_empty() {
+pub fn parse(json: &[u8]) -> Result<Value<'_>, Error> {
+    parse_with_options(json, &ParseOptions::default())
+}
+
+/// Parses JSON-compliant bytes into [`Value`] according to `options`
+/// # Errors
+/// If `json` is not valid JSON or it does not fulfill `options`.
+/// # Panics
+/// If and only if there is not enough memory to allocate.
+pub fn parse_with_options<'a>(json: &'a [u8], options: &ParseOptions) -> Result<Value<'a>, Error> {
+    let mut values = json;
+    parse_document(&mut values, options).map_err(|e| e.locate(json, values))
+}
+
+#[inline]
+pub fn parse_document<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+) -> Result<Value<'a>, ErrorKind> {
+    parse_document_in::<Borrowed>(values, options)
+}
+
+/// Parses a whole document, storing its strings and numbers with `S`
+pub fn parse_document_in<'a, 'b, S: Storage<'a, 'b>>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+) -> Result<Value<'b>, ErrorKind> {
+    let res = parse_value_in::<S>(values, options, 0)?;
+    skip_ignored(values, options)?;
+    if values.is_empty() {
         Ok(res)
     } else {
-        Err(Error::InvalidEOF)
+        Err(ErrorKind::InvalidEOF)
     }
 }
 
-pub fn parse_value<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Value<'a>, Error> {
-    skip_unused(values);
+/// Parses a value nested in `depth` arrays or objects
+#[inline]
+pub fn parse_value<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<Value<'a>, ErrorKind> {
+    parse_value_in::<Borrowed>(values, options, depth)
+}
+
+/// Parses a value nested in `depth` arrays or objects, storing its strings and numbers with `S`
+pub fn parse_value_in<'a, 'b, S: Storage<'a, 'b>>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<Value<'b>, ErrorKind> {
+    skip_ignored(values, options)?;
     let token = current_token(values)?;
     match token {
-        b'{' => parse_object(values).map(Value::Object),
-        b'[' => parse_array(values).map(Value::Array),
-        b'"' => parse_string(values).map(Value::String),
+        b'{' => parse_object::<S>(values, options, depth),
+        b'[' => parse_array::<S>(values, options, depth).map(Value::Array),
+        b'"' => parse_string(values).map(|x| Value::String(S::string(x))),
+        b'\'' if options.relaxed => {
+            parse_single_quoted(values).map(|x| Value::String(S::string(x)))
+        }
         b'n' => parse_null(values).map(|_| Value::Null),
         b't' => parse_true(values).map(|_| Value::Bool(true)),
         b'f' => parse_false(values).map(|_| Value::Bool(false)),
-        b'0'..=b'9' | b'-' => parse_number(values).map(Value::Number),
-        other => Err(Error::InvalidToken(other)),
+        b'0'..=b'9' | b'-' => {
+            parse_any_number(values, options).map(|x| Value::Number(S::number(x)))
+        }
+        b'+' | b'.' | b'I' | b'N' if options.relaxed => {
+            parse_relaxed_number(values).map(|x| Value::Number(S::number(x)))
+        }
+        other => Err(ErrorKind::InvalidToken(other)),
     }
 }
 
 #[inline]
 pub fn skip_unused(values: &mut &[u8]) {
-    while let [first, rest @ ..] = values {
-        if !matches!(first, b'\n' | b' ' | b'\r' | b'\t') {
-            break;
-        }
-        *values = rest;
+    *values = &values[whitespace_run(values)..];
+}
+
+/// Errors if a container at `depth` would exceed [`ParseOptions::max_depth`]
+#[inline]
+pub fn check_depth(options: &ParseOptions, depth: usize) -> Result<(), ErrorKind> {
+    if depth >= options.max_depth {
+        Err(ErrorKind::RecursionLimitExceeded)
+    } else {
+        Ok(())
     }
 }
 
 #[inline]
-pub fn current_token(values: &[u8]) -> Result<u8, Error> {
-    if let Some(t) = values.get(0) {
+pub fn current_token(values: &[u8]) -> Result<u8, ErrorKind> {
+    if let Some(t) = values.first() {
         Ok(*t)
     } else {
-        Err(Error::InvalidEOF)
+        Err(ErrorKind::InvalidEOF)
     }
 }
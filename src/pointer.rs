@@ -0,0 +1,178 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use super::error::*;
+use super::options::{DuplicateKeys, ParseOptions};
+use super::parser::{current_token, parse_value, Value};
+use super::relaxed::{parse_key, skip_ignored};
+use super::skip::{for_each_entry, for_each_item, skip_nested_value, validate_with_options};
+
+impl<'a> Value<'a> {
+    /// Returns the value at the JSON Pointer (RFC 6901) `pointer`, e.g. `"/a/0/b"`, if any.
+    ///
+    /// `~1` and `~0` in `pointer` refer to `/` and `~` respectively; the empty pointer refers
+    /// to the whole value. Invalid pointers, e.g. not starting with `/`, refer to no value.
+    /// A key repeated in a [`Value::MultiObject`] refers to its last value.
+    pub fn pointer(&self, pointer: &str) -> Option<&Value<'a>> {
+        split_pointer(pointer)?
+            .iter()
+            .try_fold(self, |value, token| match value {
+                Value::Object(object) => object.get(token.as_ref()),
+                Value::MultiObject(entries) => entries
+                    .iter()
+                    .rev()
+                    .find(|(key, _)| key == token)
+                    .map(|(_, value)| value),
+                Value::Array(array) => array.get(parse_index(token)?),
+                _ => None,
+            })
+    }
+
+    /// Returns a mutable reference to the value at the JSON Pointer `pointer`, if any.
+    /// See [`Value::pointer`].
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value<'a>> {
+        split_pointer(pointer)?
+            .iter()
+            .try_fold(self, |value, token| match value {
+                Value::Object(object) => object.get_mut(token.as_ref()),
+                Value::MultiObject(entries) => entries
+                    .iter_mut()
+                    .rev()
+                    .find(|(key, _)| key == token)
+                    .map(|(_, value)| value),
+                Value::Array(array) => array.get_mut(parse_index(token)?),
+                _ => None,
+            })
+    }
+}
+
+/// Returns the unescaped reference tokens of `pointer`, or `None` if it is invalid
+fn split_pointer(pointer: &str) -> Option<Vec<Cow<'_, str>>> {
+    if pointer.is_empty() {
+        return Some(vec![]);
+    }
+    pointer
+        .strip_prefix('/')?
+        .split('/')
+        .map(unescape)
+        .collect()
+}
+
+fn unescape(token: &str) -> Option<Cow<'_, str>> {
+    if !token.contains('~') {
+        return Some(Cow::Borrowed(token));
+    }
+    let mut result = String::with_capacity(token.len());
+    let mut chars = token.chars();
+    while let Some(c) = chars.next() {
+        result.push(match c {
+            '~' => match chars.next() {
+                Some('0') => '~',
+                Some('1') => '/',
+                _ => return None,
+            },
+            c => c,
+        });
+    }
+    Some(Cow::Owned(result))
+}
+
+/// Returns `token` escaped as a reference token of a JSON Pointer, the inverse of `unescape`
+pub fn escape(token: &str) -> Cow<'_, str> {
+    if !token.contains(['~', '/']) {
+        return Cow::Borrowed(token);
+    }
+    Cow::Owned(token.replace('~', "~0").replace('/', "~1"))
+}
+
+/// Returns the array index referred by `token`, which must not have leading zeros
+fn parse_index(token: &str) -> Option<usize> {
+    if !token.bytes().all(|x| x.is_ascii_digit()) || (token.len() > 1 && token.starts_with('0')) {
+        return None;
+    }
+    token.parse().ok()
+}
+
+/// Parses the value at the JSON Pointer (RFC 6901) `pointer` of `json`, skipping unrelated
+/// values without building them. See [`Value::pointer`].
+///
+/// A repeated key refers to its first value with [`DuplicateKeys::FirstWins`] and to its last
+/// value otherwise; only the returned value is checked for [`DuplicateKeys::Error`].
+/// # Errors
+/// If and only if `json` is not valid JSON, with the same error [`parse`](crate::parse) returns.
+pub fn parse_pointer<'a>(json: &'a [u8], pointer: &str) -> Result<Option<Value<'a>>, Error> {
+    parse_pointer_with_options(json, pointer, &ParseOptions::default())
+}
+
+/// Parses the value at the JSON Pointer `pointer` of `json` according to `options`.
+/// See [`parse_pointer`].
+/// # Errors
+/// If `json` is not valid JSON or it does not fulfill `options`.
+pub fn parse_pointer_with_options<'a>(
+    json: &'a [u8],
+    pointer: &str,
+    options: &ParseOptions,
+) -> Result<Option<Value<'a>>, Error> {
+    let Some(tokens) = split_pointer(pointer) else {
+        return validate_with_options(json, options).map(|_| None);
+    };
+    let mut values = json;
+    find_document(&mut values, &tokens, options).map_err(|e| e.locate(json, values))
+}
+
+fn find_document<'a>(
+    values: &mut &'a [u8],
+    tokens: &[Cow<str>],
+    options: &ParseOptions,
+) -> Result<Option<Value<'a>>, ErrorKind> {
+    let result = find(values, tokens, options, 0)?;
+    skip_ignored(values, options)?;
+    if values.is_empty() {
+        Ok(result)
+    } else {
+        Err(ErrorKind::InvalidEOF)
+    }
+}
+
+/// Consumes a value nested in `depth` arrays or objects, returning the value at `tokens`
+fn find<'a>(
+    values: &mut &'a [u8],
+    tokens: &[Cow<str>],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<Option<Value<'a>>, ErrorKind> {
+    let Some((token, tokens)) = tokens.split_first() else {
+        return parse_value(values, options, depth).map(Some);
+    };
+
+    skip_ignored(values, options)?;
+    let mut result = None;
+    match current_token(values)? {
+        b'{' => {
+            let mut found = false;
+            for_each_entry(values, options, depth, parse_key, |values, _, key| {
+                if key == *token && !(found && options.duplicate_keys == DuplicateKeys::FirstWins) {
+                    found = true;
+                    result = find(values, tokens, options, depth + 1)?;
+                } else {
+                    skip_nested_value(values, options, depth + 1)?;
+                }
+                Ok(())
+            })?
+        }
+        b'[' => {
+            let index = parse_index(token);
+            for_each_item(values, options, depth, |values, i| {
+                if Some(i) == index {
+                    result = find(values, tokens, options, depth + 1)?;
+                } else {
+                    skip_nested_value(values, options, depth + 1)?;
+                }
+                Ok(())
+            })?
+        }
+        _ => skip_nested_value(values, options, depth)?,
+    }
+    Ok(result)
+}
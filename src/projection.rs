@@ -0,0 +1,150 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::string::String;
+
+use super::error::*;
+use super::object::insert;
+use super::options::{DuplicateKeys, ParseOptions};
+use super::parser::{current_token, parse_value, Object, Value};
+use super::relaxed::{parse_key, skip_ignored};
+use super::skip::{for_each_entry, for_each_item, skip_nested_value};
+
+/// A set of key paths selecting the fields of objects to parse, used by [`parse_projected`].
+///
+/// A path selects the value at its last key, e.g. `["a", "b"]` selects `1` in `{"a": {"b": 1}}`.
+/// Arrays are transparent: the projection applies to each of their items.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Projection(Node);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    // the whole value is selected
+    All,
+    // only these fields of objects are selected
+    Fields(BTreeMap<String, Node>),
+}
+
+impl Projection {
+    /// Returns a [`Projection`] that selects the values at `paths`.
+    /// An empty path selects everything.
+    pub fn new<P, K>(paths: impl IntoIterator<Item = P>) -> Self
+    where
+        P: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        let mut root = Node::Fields(BTreeMap::new());
+        for path in paths {
+            let mut node = &mut root;
+            for key in path {
+                node = match node {
+                    Node::All => break,
+                    Node::Fields(fields) => fields
+                        .entry(key.into())
+                        .or_insert_with(|| Node::Fields(BTreeMap::new())),
+                };
+            }
+            // keys below a selected value are redundant
+            *node = Node::All;
+        }
+        Self(root)
+    }
+}
+
+/// Parses JSON-compliant bytes into a [`Value`] that only contains the fields of objects
+/// selected by `projection`. Values of other fields are skipped without being built.
+/// # Errors
+/// If and only if `json` is not valid JSON, with the same error [`parse`](crate::parse) returns.
+pub fn parse_projected<'a>(json: &'a [u8], projection: &Projection) -> Result<Value<'a>, Error> {
+    parse_projected_with_options(json, projection, &ParseOptions::default())
+}
+
+/// Parses JSON-compliant bytes according to `options` into a [`Value`] that only contains
+/// the fields of objects selected by `projection`. See [`parse_projected`].
+/// # Errors
+/// If `json` is not valid JSON or it does not fulfill `options`.
+pub fn parse_projected_with_options<'a>(
+    json: &'a [u8],
+    projection: &Projection,
+    options: &ParseOptions,
+) -> Result<Value<'a>, Error> {
+    let mut values = json;
+    project_document(&mut values, &projection.0, options).map_err(|e| e.locate(json, values))
+}
+
+fn project_document<'a>(
+    values: &mut &'a [u8],
+    node: &Node,
+    options: &ParseOptions,
+) -> Result<Value<'a>, ErrorKind> {
+    let value = project(values, node, options, 0)?;
+    skip_ignored(values, options)?;
+    if values.is_empty() {
+        Ok(value)
+    } else {
+        Err(ErrorKind::InvalidEOF)
+    }
+}
+
+/// Parses a value nested in `depth` arrays or objects, keeping only the fields selected by `node`
+fn project<'a>(
+    values: &mut &'a [u8],
+    node: &Node,
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<Value<'a>, ErrorKind> {
+    let fields = match node {
+        Node::All => return parse_value(values, options, depth),
+        Node::Fields(fields) => fields,
+    };
+
+    skip_ignored(values, options)?;
+    match current_token(values)? {
+        b'{' => {
+            let policy = options.duplicate_keys;
+            let mut items = Object::new();
+            let mut entries = vec![];
+            // keys of the skipped fields, to find them repeated
+            let mut skipped = BTreeSet::new();
+            for_each_entry(
+                values,
+                options,
+                depth,
+                parse_key,
+                |values, key_start, key| {
+                    match fields.get(key.as_ref()) {
+                        Some(node) => {
+                            let value = project(values, node, options, depth + 1)?;
+                            if policy == DuplicateKeys::KeepAll {
+                                entries.push((key, value));
+                            } else if let Err(error) = insert(&mut items, key, value, policy) {
+                                *values = key_start;
+                                return Err(error);
+                            }
+                        }
+                        None => {
+                            skip_nested_value(values, options, depth + 1)?;
+                            if policy == DuplicateKeys::Error && !skipped.insert(key.clone()) {
+                                *values = key_start;
+                                return Err(ErrorKind::DuplicateKey(key.into_owned()));
+                            }
+                        }
+                    }
+                    Ok(())
+                },
+            )?;
+            Ok(if policy == DuplicateKeys::KeepAll {
+                Value::MultiObject(entries)
+            } else {
+                Value::Object(items)
+            })
+        }
+        b'[' => {
+            let mut items = vec![];
+            for_each_item(values, options, depth, |values, _| {
+                items.push(project(values, node, options, depth + 1)?);
+                Ok(())
+            })?;
+            Ok(Value::Array(items))
+        }
+        _ => parse_value(values, options, depth),
+    }
+}
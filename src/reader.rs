@@ -0,0 +1,227 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use super::boolean::{parse_false, parse_true};
+use super::error::*;
+use super::null::parse_null;
+use super::options::ParseOptions;
+use super::parser::{check_depth, current_token, Number};
+use super::relaxed::{
+    is_number_start, is_trailing_comma, parse_any_number, parse_key, skip_ignored,
+};
+use super::string::{parse_single_quoted, parse_string};
+
+/// An event emitted by a [`Reader`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'a> {
+    /// The start of an object (`{`)
+    StartObject,
+    /// The key of the next value of an object
+    Key(Cow<'a, str>),
+    /// The end of an object (`}`)
+    EndObject,
+    /// The start of an array (`[`)
+    StartArray,
+    /// The end of an array (`]`)
+    EndArray,
+    /// A string
+    String(Cow<'a, str>),
+    /// A number
+    Number(Number<'a>),
+    /// A bool
+    Bool(bool),
+    /// A `null`
+    Null,
+}
+
+/// A container the parser is in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Container {
+    Array,
+    Object,
+}
+
+/// A pull-based reader that yields the [`Event`]s of a JSON document without building a
+/// [`Value`](crate::Value).
+///
+/// Memory usage is proportional to the nesting depth of the document.
+/// Errors are the same as [`parse`](crate::parse) returns and the reader stops after the first one.
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    json: &'a [u8],
+    remaining: &'a [u8],
+    options: ParseOptions,
+    // the containers the next event is nested in
+    stack: Vec<Container>,
+    // whether the next item is the first of its container
+    first: bool,
+    // whether a key was just read, and thus a value is next
+    after_key: bool,
+    started: bool,
+    finished: bool,
+}
+
+impl<'a> Reader<'a> {
+    /// Returns a new [`Reader`] of `json`
+    pub fn new(json: &'a [u8]) -> Self {
+        Self::with_options(json, ParseOptions::default())
+    }
+
+    /// Returns a new [`Reader`] of `json` that parses according to `options`
+    pub fn with_options(json: &'a [u8], options: ParseOptions) -> Self {
+        Self {
+            json,
+            remaining: json,
+            options,
+            stack: vec![],
+            first: true,
+            after_key: false,
+            started: false,
+            finished: false,
+        }
+    }
+
+    /// The number of bytes of the input consumed so far.
+    pub fn byte_offset(&self) -> usize {
+        self.json.len() - self.remaining.len()
+    }
+
+    /// The number of arrays and objects the next event is nested in
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    fn step(&mut self) -> Result<Option<Event<'a>>, ErrorKind> {
+        if self.after_key {
+            self.after_key = false;
+            return self.value().map(Some);
+        }
+        skip_ignored(&mut self.remaining, &self.options)?;
+        let container = match self.stack.last() {
+            Some(container) => *container,
+            None if self.started => {
+                return if self.remaining.is_empty() {
+                    Ok(None)
+                } else {
+                    Err(ErrorKind::InvalidEOF)
+                };
+            }
+            None => {
+                self.started = true;
+                return self.value().map(Some);
+            }
+        };
+
+        let token = current_token(self.remaining)?;
+        let end = match container {
+            Container::Array => b']',
+            Container::Object => b'}',
+        };
+        if token == end {
+            self.remaining = &self.remaining[1..];
+            return Ok(Some(self.end(container)));
+        }
+        if !self.first {
+            if token != b',' {
+                return Err(ErrorKind::MissingComma(token));
+            }
+            self.remaining = &self.remaining[1..]; // consume ","
+            if is_trailing_comma(&mut self.remaining, end, &self.options)? {
+                return Ok(Some(self.end(container)));
+            }
+        }
+        self.first = false;
+
+        match container {
+            Container::Array => self.value().map(Some),
+            Container::Object => self.key().map(Some),
+        }
+    }
+
+    /// Leaves `container`, whose end was consumed
+    fn end(&mut self, container: Container) -> Event<'a> {
+        self.stack.pop();
+        // the container was an item of its parent
+        self.first = false;
+        match container {
+            Container::Array => Event::EndArray,
+            Container::Object => Event::EndObject,
+        }
+    }
+
+    fn key(&mut self) -> Result<Event<'a>, ErrorKind> {
+        skip_ignored(&mut self.remaining, &self.options)?;
+        let key = parse_key(&mut self.remaining, &self.options)?;
+
+        skip_ignored(&mut self.remaining, &self.options)?;
+        let token = current_token(self.remaining)?;
+        if token != b':' {
+            return Err(ErrorKind::InvalidToken(token));
+        };
+        self.remaining = &self.remaining[1..];
+        self.after_key = true;
+        Ok(Event::Key(key))
+    }
+
+    fn value(&mut self) -> Result<Event<'a>, ErrorKind> {
+        let values = &mut self.remaining;
+        skip_ignored(values, &self.options)?;
+        let token = current_token(values)?;
+        match token {
+            b'{' | b'[' => {
+                check_depth(&self.options, self.stack.len())?;
+                *values = &values[1..];
+                self.first = true;
+                if token == b'{' {
+                    self.stack.push(Container::Object);
+                    Ok(Event::StartObject)
+                } else {
+                    self.stack.push(Container::Array);
+                    Ok(Event::StartArray)
+                }
+            }
+            other => parse_scalar(values, other, &self.options),
+        }
+    }
+}
+
+/// Parses a value that is not an array or object, starting with `token`
+#[inline]
+pub fn parse_scalar<'a>(
+    values: &mut &'a [u8],
+    token: u8,
+    options: &ParseOptions,
+) -> Result<Event<'a>, ErrorKind> {
+    match token {
+        b'"' => parse_string(values).map(Event::String),
+        b'\'' if options.relaxed => parse_single_quoted(values).map(Event::String),
+        b'n' => parse_null(values).map(|_| Event::Null),
+        b't' => parse_true(values).map(|_| Event::Bool(true)),
+        b'f' => parse_false(values).map(|_| Event::Bool(false)),
+        token if is_number_start(token, options) => {
+            parse_any_number(values, options).map(Event::Number)
+        }
+        other => Err(ErrorKind::InvalidToken(other)),
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<Event<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.step() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e.locate(self.json, self.remaining)))
+            }
+        }
+    }
+}
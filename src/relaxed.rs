@@ -0,0 +1,125 @@
+use alloc::borrow::Cow;
+
+use super::error::*;
+use super::number::{parse_number, parse_relaxed_number};
+use super::options::ParseOptions;
+use super::parser::{current_token, skip_unused, Number};
+use super::string::{parse_single_quoted, parse_string, skip_single_quoted, skip_string};
+
+/// Skips whitespace and, with [`ParseOptions::relaxed`], comments
+#[inline]
+pub fn skip_ignored(values: &mut &[u8], options: &ParseOptions) -> Result<(), ErrorKind> {
+    skip_unused(values);
+    if options.relaxed {
+        skip_comments(values)?;
+    }
+    Ok(())
+}
+
+/// Skips `//` and `/* */` comments and the whitespace after them
+fn skip_comments(values: &mut &[u8]) -> Result<(), ErrorKind> {
+    loop {
+        match *values {
+            [b'/', b'/', rest @ ..] => {
+                let end = rest.iter().position(|x| *x == b'\n').unwrap_or(rest.len());
+                *values = &rest[end..];
+            }
+            [b'/', b'*', rest @ ..] => match rest.windows(2).position(|x| x == b"*/") {
+                Some(end) => *values = &rest[end + 2..],
+                None => {
+                    *values = &rest[rest.len()..];
+                    return Err(ErrorKind::InvalidEOF);
+                }
+            },
+            _ => return Ok(()),
+        }
+        skip_unused(values);
+    }
+}
+
+/// Parses a number, of the relaxed syntax with [`ParseOptions::relaxed`]
+#[inline]
+pub fn parse_any_number<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+) -> Result<Number<'a>, ErrorKind> {
+    if options.relaxed {
+        parse_relaxed_number(values)
+    } else {
+        parse_number(values)
+    }
+}
+
+/// Whether `token` starts a number, of the relaxed syntax with [`ParseOptions::relaxed`]
+#[inline]
+pub fn is_number_start(token: u8, options: &ParseOptions) -> bool {
+    matches!(token, b'0'..=b'9' | b'-')
+        || options.relaxed && matches!(token, b'+' | b'.' | b'I' | b'N')
+}
+
+/// Parses the key of an object: a string or, with [`ParseOptions::relaxed`], a single-quoted
+/// string or an identifier
+#[inline]
+pub fn parse_key<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+) -> Result<Cow<'a, str>, ErrorKind> {
+    match current_token(values)? {
+        b'"' => parse_string(values),
+        b'\'' if options.relaxed => parse_single_quoted(values),
+        token if options.relaxed && is_identifier_start(token) => {
+            Ok(Cow::Borrowed(parse_identifier(values)))
+        }
+        token => Err(ErrorKind::InvalidStringToken(token)),
+    }
+}
+
+/// Skips the key of an object without allocating, erroring like [`parse_key`]
+#[inline]
+pub fn skip_key(values: &mut &[u8], options: &ParseOptions) -> Result<(), ErrorKind> {
+    match current_token(values)? {
+        b'"' => skip_string(values),
+        b'\'' if options.relaxed => skip_single_quoted(values),
+        token if options.relaxed && is_identifier_start(token) => {
+            parse_identifier(values);
+            Ok(())
+        }
+        token => Err(ErrorKind::InvalidStringToken(token)),
+    }
+}
+
+#[inline]
+fn is_identifier_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte == b'$'
+}
+
+/// Parses an identifier of ASCII letters, digits, `_` and `$`
+fn parse_identifier<'a>(values: &mut &'a [u8]) -> &'a str {
+    let length = values
+        .iter()
+        .position(|x| !(is_identifier_start(*x) || x.is_ascii_digit()))
+        .unwrap_or(values.len());
+    let (identifier, rest) = values.split_at(length);
+    *values = rest;
+    // identifiers only contain ASCII
+    core::str::from_utf8(identifier).unwrap()
+}
+
+/// With [`ParseOptions::relaxed`], returns whether the comma just consumed is trailing, i.e.
+/// followed by `end`, which is then consumed
+#[inline]
+pub fn is_trailing_comma(
+    values: &mut &[u8],
+    end: u8,
+    options: &ParseOptions,
+) -> Result<bool, ErrorKind> {
+    if !options.relaxed {
+        return Ok(false);
+    }
+    skip_ignored(values, options)?;
+    let is_trailing = current_token(values)? == end;
+    if is_trailing {
+        *values = &values[1..];
+    }
+    Ok(is_trailing)
+}
@@ -0,0 +1,708 @@
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use super::error::ErrorKind;
+use super::parser::{Number, Object, Value};
+use super::pointer::escape;
+
+/// A JSON Schema (draft 2020-12) compiled by [`JsonSchema::compile`] to validate [`Value`]s.
+///
+/// The supported keywords are `type`, `enum`, `const`, `properties`, `required`,
+/// `additionalProperties`, `prefixItems`, `items`, `minItems`, `maxItems`, `minimum`, `maximum`,
+/// `exclusiveMinimum`, `exclusiveMaximum`, `minLength`, `maxLength`, `allOf`, `anyOf`, `oneOf`,
+/// `not` and `$ref` to a JSON Pointer within the document, e.g. `"#/$defs/a"`, which may be
+/// percent-encoded, e.g. `"#/$defs/a%20b"`. Other keywords are ignored. Numbers are compared
+/// as `f64`, except integers in `enum` and `const`.
+#[derive(Debug, Clone)]
+pub struct JsonSchema {
+    // the compiled (sub)schemas, starting with the root
+    nodes: Vec<Node>,
+}
+
+/// An error of a schema document that [`JsonSchema::compile`] does not support
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SchemaError {
+    /// JSON Pointer to the invalid part of the schema document
+    pub path: String,
+    /// What is invalid
+    pub message: String,
+}
+
+impl Display for SchemaError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at \"{}\"", self.message, self.path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaError {}
+
+/// A part of a [`Value`] that does not conform to a [`JsonSchema`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationError {
+    /// JSON Pointer to the invalid part of the value
+    pub instance_path: String,
+    /// JSON Pointer to the keyword of the schema document that failed
+    pub schema_path: String,
+    /// Why it is invalid
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} at \"{}\"", self.message, self.instance_path)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Type {
+    Null,
+    Boolean,
+    Object,
+    Array,
+    Number,
+    String,
+    Integer,
+}
+
+impl Type {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "null" => Self::Null,
+            "boolean" => Self::Boolean,
+            "object" => Self::Object,
+            "array" => Self::Array,
+            "number" => Self::Number,
+            "string" => Self::String,
+            "integer" => Self::Integer,
+            _ => return None,
+        })
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Boolean => "boolean",
+            Self::Object => "object",
+            Self::Array => "array",
+            Self::Number => "number",
+            Self::String => "string",
+            Self::Integer => "integer",
+        }
+    }
+
+    fn matches(self, value: &Value) -> bool {
+        match (self, value) {
+            (Self::Null, Value::Null)
+            | (Self::Boolean, Value::Bool(_))
+            | (Self::Object, Value::Object(_) | Value::MultiObject(_))
+            | (Self::Array, Value::Array(_))
+            | (Self::Number, Value::Number(_))
+            | (Self::String, Value::String(_)) => true,
+            // numbers with a zero fractional part, e.g. `1.0`, are integers
+            (Self::Integer, Value::Number(Number::Integer(..))) => true,
+            (Self::Integer, Value::Number(number)) => match number.to_i128() {
+                Ok(_) => true,
+                // finite floats beyond `i128` have no fractional part
+                Err(ErrorKind::NumberOverflow) => to_f64(number).is_finite(),
+                Err(_) => false,
+            },
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Node {
+    // JSON Pointer to this schema in the schema document
+    path: String,
+    keywords: Vec<Keyword>,
+}
+
+#[derive(Debug, Clone)]
+enum Keyword {
+    // the `false` schema
+    False,
+    Type(Vec<Type>),
+    Enum(Vec<Value<'static>>),
+    Const(Value<'static>),
+    Properties(Vec<(String, usize)>),
+    Required(Vec<String>),
+    AdditionalProperties {
+        // the keys of `properties`, which are not additional
+        known: Vec<String>,
+        schema: usize,
+    },
+    PrefixItems(Vec<usize>),
+    Items {
+        // the number of items validated by `prefixItems`
+        skip: usize,
+        schema: usize,
+    },
+    MinItems(usize),
+    MaxItems(usize),
+    Minimum(f64),
+    Maximum(f64),
+    ExclusiveMinimum(f64),
+    ExclusiveMaximum(f64),
+    MinLength(usize),
+    MaxLength(usize),
+    AllOf(Vec<usize>),
+    AnyOf(Vec<usize>),
+    OneOf(Vec<usize>),
+    Not(usize),
+    Ref(usize),
+}
+
+impl Keyword {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::False => "",
+            Self::Type(_) => "type",
+            Self::Enum(_) => "enum",
+            Self::Const(_) => "const",
+            Self::Properties(_) => "properties",
+            Self::Required(_) => "required",
+            Self::AdditionalProperties { .. } => "additionalProperties",
+            Self::PrefixItems(_) => "prefixItems",
+            Self::Items { .. } => "items",
+            Self::MinItems(_) => "minItems",
+            Self::MaxItems(_) => "maxItems",
+            Self::Minimum(_) => "minimum",
+            Self::Maximum(_) => "maximum",
+            Self::ExclusiveMinimum(_) => "exclusiveMinimum",
+            Self::ExclusiveMaximum(_) => "exclusiveMaximum",
+            Self::MinLength(_) => "minLength",
+            Self::MaxLength(_) => "maxLength",
+            Self::AllOf(_) => "allOf",
+            Self::AnyOf(_) => "anyOf",
+            Self::OneOf(_) => "oneOf",
+            Self::Not(_) => "not",
+            Self::Ref(_) => "$ref",
+        }
+    }
+
+    /// Returns the schemas applied to the same value as this keyword's schema
+    fn in_place(&self) -> &[usize] {
+        match self {
+            Self::AllOf(schemas) | Self::AnyOf(schemas) | Self::OneOf(schemas) => schemas,
+            Self::Not(schema) | Self::Ref(schema) => core::slice::from_ref(schema),
+            _ => &[],
+        }
+    }
+}
+
+/// Returns `fragment`, the fragment of a URI, with its `%XX` escapes decoded, or `None` if an
+/// escape is invalid or the result is not UTF-8
+fn percent_decode(fragment: &str) -> Option<Cow<'_, str>> {
+    if !fragment.contains('%') {
+        return Some(Cow::Borrowed(fragment));
+    }
+    let digit = |byte: u8| (byte as char).to_digit(16);
+    let mut bytes = Vec::with_capacity(fragment.len());
+    let mut rest = fragment.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        if byte == b'%' {
+            let [high, low, tail @ ..] = rest else {
+                return None;
+            };
+            bytes.push((digit(*high)? * 16 + digit(*low)?) as u8);
+            rest = tail;
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok().map(Cow::Owned)
+}
+
+fn schema_error(path: &str, message: impl ToString) -> SchemaError {
+    SchemaError {
+        path: path.to_string(),
+        message: message.to_string(),
+    }
+}
+
+/// Returns the items of `value` if it is an object
+fn entries<'b, 'a>(value: &'b Value<'a>) -> Option<Vec<(&'b str, &'b Value<'a>)>> {
+    match value {
+        Value::Object(object) => Some(object.iter().map(|(k, v)| (k.as_ref(), v)).collect()),
+        Value::MultiObject(entries) => Some(entries.iter().map(|(k, v)| (k.as_ref(), v)).collect()),
+        _ => None,
+    }
+}
+
+/// Returns the value of `number`, saturating to infinity or zero when it does not fit
+fn to_f64(number: &Number) -> f64 {
+    match number.to_f64() {
+        Ok(value) => value,
+        Err(ErrorKind::NumberOverflow) => {
+            let (Number::Integer(mantissa, _) | Number::Float(mantissa, _)) = number;
+            if mantissa.starts_with(b"-") {
+                f64::NEG_INFINITY
+            } else {
+                f64::INFINITY
+            }
+        }
+        Err(_) => 0.0,
+    }
+}
+
+fn numbers_equal(a: &Number, b: &Number) -> bool {
+    match (a.to_i128(), b.to_i128()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => to_f64(a) == to_f64(b),
+    }
+}
+
+/// Returns whether `a` and `b` are equal JSON values, e.g. `1` and `1.0`
+fn equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Null, Value::Null) => true,
+        (Value::Bool(a), Value::Bool(b)) => a == b,
+        (Value::Number(a), Value::Number(b)) => numbers_equal(a, b),
+        (Value::String(a), Value::String(b)) => a == b,
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equal(a, b))
+        }
+        (a, b) => match (entries(a), entries(b)) {
+            (Some(a), Some(b)) => {
+                // a repeated key counts once, with its last value
+                let a = a.into_iter().collect::<BTreeMap<_, _>>();
+                let b = b.into_iter().collect::<BTreeMap<_, _>>();
+                a.len() == b.len()
+                    && a.iter()
+                        .all(|(key, a)| b.get(key).is_some_and(|b| equal(a, b)))
+            }
+            _ => false,
+        },
+    }
+}
+
+struct Compiler<'b, 'a> {
+    root: &'b Value<'a>,
+    nodes: Vec<Node>,
+    // the index of each compiled schema by its path
+    paths: BTreeMap<String, usize>,
+}
+
+impl<'b, 'a> Compiler<'b, 'a> {
+    /// Compiles the schema `schema` at `path`, returning its index
+    fn compile(&mut self, schema: &'b Value<'a>, path: String) -> Result<usize, SchemaError> {
+        if let Some(index) = self.paths.get(&path) {
+            return Ok(*index);
+        }
+        let index = self.nodes.len();
+        self.paths.insert(path.clone(), index);
+        self.nodes.push(Node {
+            path: path.clone(),
+            keywords: vec![],
+        });
+        let keywords = match schema {
+            Value::Bool(true) => vec![],
+            Value::Bool(false) => vec![Keyword::False],
+            Value::Object(object) => self.keywords(object, &path)?,
+            _ => return Err(schema_error(&path, "schemas must be objects or booleans")),
+        };
+        self.nodes[index].keywords = keywords;
+        Ok(index)
+    }
+
+    fn subschema(&mut self, schema: &'b Value<'a>, path: &str) -> Result<usize, SchemaError> {
+        self.compile(schema, path.to_string())
+    }
+
+    /// Compiles a non-empty array of schemas
+    fn subschemas(
+        &mut self,
+        schemas: &'b Value<'a>,
+        path: &str,
+    ) -> Result<Vec<usize>, SchemaError> {
+        match schemas {
+            Value::Array(schemas) if !schemas.is_empty() => schemas
+                .iter()
+                .enumerate()
+                .map(|(i, schema)| self.compile(schema, format!("{path}/{i}")))
+                .collect(),
+            _ => Err(schema_error(path, "expected a non-empty array of schemas")),
+        }
+    }
+
+    fn keywords(
+        &mut self,
+        object: &'b Object<'a>,
+        path: &str,
+    ) -> Result<Vec<Keyword>, SchemaError> {
+        let mut keywords = vec![];
+        let path = |keyword: &str| format!("{path}/{keyword}");
+        for (keyword, value) in object {
+            let path = path(keyword);
+            let path = path.as_str();
+            keywords.push(match keyword.as_ref() {
+                "type" => Keyword::Type(types(value, path)?),
+                "enum" => match value {
+                    Value::Array(values) => {
+                        Keyword::Enum(values.iter().cloned().map(Value::into_owned).collect())
+                    }
+                    _ => return Err(schema_error(path, "expected an array")),
+                },
+                "const" => Keyword::Const(value.clone().into_owned()),
+                "properties" => {
+                    let properties =
+                        entries(value).ok_or_else(|| schema_error(path, "expected an object"))?;
+                    Keyword::Properties(
+                        properties
+                            .into_iter()
+                            .map(|(key, schema)| {
+                                let index =
+                                    self.compile(schema, format!("{path}/{}", escape(key)))?;
+                                Ok((key.to_string(), index))
+                            })
+                            .collect::<Result<_, _>>()?,
+                    )
+                }
+                "required" => Keyword::Required(strings(value, path)?),
+                "additionalProperties" => Keyword::AdditionalProperties {
+                    known: match object.get("properties").and_then(entries) {
+                        Some(properties) => properties.iter().map(|(k, _)| k.to_string()).collect(),
+                        None => vec![],
+                    },
+                    schema: self.subschema(value, path)?,
+                },
+                "prefixItems" => Keyword::PrefixItems(self.subschemas(value, path)?),
+                "items" => Keyword::Items {
+                    skip: match object.get("prefixItems") {
+                        Some(Value::Array(schemas)) => schemas.len(),
+                        _ => 0,
+                    },
+                    schema: self.subschema(value, path)?,
+                },
+                "minItems" => Keyword::MinItems(non_negative(value, path)?),
+                "maxItems" => Keyword::MaxItems(non_negative(value, path)?),
+                "minimum" => Keyword::Minimum(number(value, path)?),
+                "maximum" => Keyword::Maximum(number(value, path)?),
+                "exclusiveMinimum" => Keyword::ExclusiveMinimum(number(value, path)?),
+                "exclusiveMaximum" => Keyword::ExclusiveMaximum(number(value, path)?),
+                "minLength" => Keyword::MinLength(non_negative(value, path)?),
+                "maxLength" => Keyword::MaxLength(non_negative(value, path)?),
+                "allOf" => Keyword::AllOf(self.subschemas(value, path)?),
+                "anyOf" => Keyword::AnyOf(self.subschemas(value, path)?),
+                "oneOf" => Keyword::OneOf(self.subschemas(value, path)?),
+                "not" => Keyword::Not(self.subschema(value, path)?),
+                "$ref" => Keyword::Ref(self.reference(value, path)?),
+                _ => continue,
+            });
+        }
+        Ok(keywords)
+    }
+
+    fn reference(&mut self, reference: &Value, path: &str) -> Result<usize, SchemaError> {
+        let Value::String(reference) = reference else {
+            return Err(schema_error(path, "expected a string"));
+        };
+        let fragment = reference.strip_prefix('#').ok_or_else(|| {
+            schema_error(path, "only JSON Pointers within the document are supported")
+        })?;
+        let pointer = percent_decode(fragment)
+            .ok_or_else(|| schema_error(path, "invalid percent-encoding"))?;
+        if !(pointer.is_empty() || pointer.starts_with('/')) {
+            return Err(schema_error(
+                path,
+                "only JSON Pointers within the document are supported",
+            ));
+        }
+        let root = self.root;
+        let schema = root
+            .pointer(&pointer)
+            .ok_or_else(|| schema_error(path, format!("\"{reference}\" does not exist")))?;
+        self.compile(schema, pointer.to_string())
+    }
+
+    /// Errors if a schema applies itself to the same value through `$ref`, `allOf`, `anyOf`,
+    /// `oneOf` or `not`, which would never end
+    fn check_cycles(&self) -> Result<(), SchemaError> {
+        // 0: not visited, 1: being visited, 2: visited
+        let mut states = vec![0u8; self.nodes.len()];
+        for start in 0..self.nodes.len() {
+            if states[start] != 0 {
+                continue;
+            }
+            // iterative depth-first search of (node, next edge)
+            let mut stack = vec![(start, 0)];
+            states[start] = 1;
+            while let Some((node, edge)) = stack.pop() {
+                let next = self.nodes[node]
+                    .keywords
+                    .iter()
+                    .flat_map(Keyword::in_place)
+                    .nth(edge);
+                match next {
+                    Some(&next) => {
+                        stack.push((node, edge + 1));
+                        match states[next] {
+                            0 => {
+                                states[next] = 1;
+                                stack.push((next, 0));
+                            }
+                            1 => {
+                                return Err(schema_error(
+                                    &self.nodes[next].path,
+                                    "the schema is applied to itself",
+                                ))
+                            }
+                            _ => {}
+                        }
+                    }
+                    None => states[node] = 2,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn types(value: &Value, path: &str) -> Result<Vec<Type>, SchemaError> {
+    let names = match value {
+        Value::String(name) => vec![name.to_string()],
+        _ => strings(value, path)?,
+    };
+    names
+        .iter()
+        .map(|name| {
+            Type::from_name(name)
+                .ok_or_else(|| schema_error(path, format!("unknown type \"{name}\"")))
+        })
+        .collect()
+}
+
+fn strings(value: &Value, path: &str) -> Result<Vec<String>, SchemaError> {
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .map(|value| match value {
+                Value::String(value) => Ok(value.to_string()),
+                _ => Err(schema_error(path, "expected an array of strings")),
+            })
+            .collect(),
+        _ => Err(schema_error(path, "expected an array of strings")),
+    }
+}
+
+fn number(value: &Value, path: &str) -> Result<f64, SchemaError> {
+    match value {
+        Value::Number(number) => Ok(to_f64(number)),
+        _ => Err(schema_error(path, "expected a number")),
+    }
+}
+
+fn non_negative(value: &Value, path: &str) -> Result<usize, SchemaError> {
+    match value {
+        Value::Number(number) => number
+            .to_u64()
+            .ok()
+            .and_then(|x| usize::try_from(x).ok())
+            .ok_or_else(|| schema_error(path, "expected a non-negative integer")),
+        _ => Err(schema_error(path, "expected a non-negative integer")),
+    }
+}
+
+impl JsonSchema {
+    /// Compiles the schema document `schema`, e.g. returned by [`parse`](crate::parse).
+    /// # Errors
+    /// If `schema` is not a valid schema, e.g. `"type": 1`, or it uses unsupported features,
+    /// e.g. `$ref` to another document.
+    pub fn compile(schema: &Value) -> Result<Self, SchemaError> {
+        let mut compiler = Compiler {
+            root: schema,
+            nodes: vec![],
+            paths: BTreeMap::new(),
+        };
+        compiler.compile(schema, String::new())?;
+        compiler.check_cycles()?;
+        Ok(Self {
+            nodes: compiler.nodes,
+        })
+    }
+
+    /// Validates `value` against this schema.
+    /// # Errors
+    /// All the parts of `value` that do not conform to this schema.
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<ValidationError>> {
+        let mut errors = vec![];
+        self.check(0, value, &mut String::new(), &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Returns whether `value` conforms to this schema
+    pub fn is_valid(&self, value: &Value) -> bool {
+        self.validate(value).is_ok()
+    }
+
+    fn is_valid_at(&self, node: usize, value: &Value, path: &mut String) -> bool {
+        let mut errors = vec![];
+        self.check(node, value, path, &mut errors);
+        errors.is_empty()
+    }
+
+    /// Validates `value` at `path` against the schema `node`, adding errors to `errors`
+    fn check(
+        &self,
+        node: usize,
+        value: &Value,
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let node = &self.nodes[node];
+        for keyword in &node.keywords {
+            if let Some(message) = self.check_keyword(keyword, value, path, errors) {
+                let schema_path = match keyword {
+                    Keyword::False => node.path.clone(),
+                    keyword => format!("{}/{}", node.path, keyword.name()),
+                };
+                errors.push(ValidationError {
+                    instance_path: path.clone(),
+                    schema_path,
+                    message,
+                })
+            }
+        }
+    }
+
+    /// Validates `value` at `path` against `keyword`, returning why it is invalid if it is.
+    /// Errors of subschemas are added to `errors`.
+    fn check_keyword(
+        &self,
+        keyword: &Keyword,
+        value: &Value,
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+    ) -> Option<String> {
+        match (keyword, value) {
+            (Keyword::False, _) => Some("no value is allowed".to_string()),
+            (Keyword::Type(types), value) => (!types.iter().any(|x| x.matches(value))).then(|| {
+                let names = types.iter().map(|x| x.name()).collect::<Vec<_>>();
+                format!("expected {}", names.join(" or "))
+            }),
+            (Keyword::Enum(values), value) => (!values.iter().any(|x| equal(x, value)))
+                .then(|| "expected one of the values of enum".to_string()),
+            (Keyword::Const(expected), value) => {
+                (!equal(expected, value)).then(|| "expected the value of const".to_string())
+            }
+            (Keyword::Required(names), value) => {
+                let entries = entries(value)?;
+                let missing = names
+                    .iter()
+                    .filter(|name| !entries.iter().any(|(key, _)| key == name))
+                    .map(|name| format!("\"{name}\""))
+                    .collect::<Vec<_>>();
+                (!missing.is_empty())
+                    .then(|| format!("missing required properties {}", missing.join(", ")))
+            }
+            (Keyword::MinItems(limit), Value::Array(items)) => {
+                (items.len() < *limit).then(|| format!("expected at least {limit} items"))
+            }
+            (Keyword::MaxItems(limit), Value::Array(items)) => {
+                (items.len() > *limit).then(|| format!("expected at most {limit} items"))
+            }
+            (Keyword::Minimum(limit), Value::Number(number)) => {
+                (to_f64(number) < *limit).then(|| format!("expected a number at least {limit}"))
+            }
+            (Keyword::Maximum(limit), Value::Number(number)) => {
+                (to_f64(number) > *limit).then(|| format!("expected a number at most {limit}"))
+            }
+            (Keyword::ExclusiveMinimum(limit), Value::Number(number)) => (to_f64(number) <= *limit)
+                .then(|| format!("expected a number greater than {limit}")),
+            (Keyword::ExclusiveMaximum(limit), Value::Number(number)) => {
+                (to_f64(number) >= *limit).then(|| format!("expected a number less than {limit}"))
+            }
+            // the length of strings is their number of code points
+            (Keyword::MinLength(limit), Value::String(string)) => (string.chars().count() < *limit)
+                .then(|| format!("expected at least {limit} characters")),
+            (Keyword::MaxLength(limit), Value::String(string)) => (string.chars().count() > *limit)
+                .then(|| format!("expected at most {limit} characters")),
+            (Keyword::AnyOf(schemas), value) => {
+                (!schemas.iter().any(|x| self.is_valid_at(*x, value, path)))
+                    .then(|| "expected a value valid against any schema of anyOf".to_string())
+            }
+            (Keyword::OneOf(schemas), value) => {
+                let valid = schemas
+                    .iter()
+                    .filter(|x| self.is_valid_at(**x, value, path))
+                    .count();
+                (valid != 1).then(|| {
+                    format!("expected a value valid against one schema of oneOf, found {valid}")
+                })
+            }
+            (Keyword::Not(schema), value) => self
+                .is_valid_at(*schema, value, path)
+                .then(|| "expected a value invalid against the schema of not".to_string()),
+            (Keyword::AllOf(schemas), value) => {
+                for schema in schemas {
+                    self.check(*schema, value, path, errors)
+                }
+                None
+            }
+            (Keyword::Ref(schema), value) => {
+                self.check(*schema, value, path, errors);
+                None
+            }
+            (Keyword::Properties(properties), value) => {
+                for (key, value) in entries(value)? {
+                    if let Some((_, schema)) = properties.iter().find(|(name, _)| name == key) {
+                        self.check_child(*schema, value, key, path, errors)
+                    }
+                }
+                None
+            }
+            (Keyword::AdditionalProperties { known, schema }, value) => {
+                for (key, value) in entries(value)? {
+                    if !known.iter().any(|name| name == key) {
+                        self.check_child(*schema, value, key, path, errors)
+                    }
+                }
+                None
+            }
+            (Keyword::PrefixItems(schemas), Value::Array(items)) => {
+                for (index, (item, schema)) in items.iter().zip(schemas).enumerate() {
+                    self.check_child(*schema, item, &index.to_string(), path, errors)
+                }
+                None
+            }
+            (Keyword::Items { skip, schema }, Value::Array(items)) => {
+                for (index, item) in items.iter().enumerate().skip(*skip) {
+                    self.check_child(*schema, item, &index.to_string(), path, errors)
+                }
+                None
+            }
+            // keywords that do not apply to the type of `value`
+            _ => None,
+        }
+    }
+
+    /// Validates `value`, the item `token` of the value at `path`, against the schema `node`
+    fn check_child(
+        &self,
+        node: usize,
+        value: &Value,
+        token: &str,
+        path: &mut String,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        let length = path.len();
+        path.push('/');
+        path.push_str(&escape(token));
+        self.check(node, value, path, errors);
+        path.truncate(length);
+    }
+}
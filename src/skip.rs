@@ -0,0 +1,219 @@
+use super::boolean::{parse_false, parse_true};
+use super::error::*;
+use super::null::parse_null;
+use super::number::parse_relaxed_number;
+use super::options::ParseOptions;
+use super::parser::{check_depth, current_token};
+use super::relaxed::{is_trailing_comma, parse_any_number, skip_ignored, skip_key};
+use super::string::{skip_single_quoted, skip_string, skip_validated_string};
+use super::structural::structural_run;
+
+/// Validates that `json` is JSON-compliant, without allocating.
+/// # Errors
+/// If and only if `json` is not valid JSON, with the same error [`parse`](crate::parse) returns.
+pub fn validate(json: &[u8]) -> Result<(), Error> {
+    validate_with_options(json, &ParseOptions::default())
+}
+
+/// Validates that `json` is JSON-compliant and fulfills `options`, without allocating.
+/// # Errors
+/// If `json` is not valid JSON or it does not fulfill `options`, with the same error
+/// [`parse_with_options`](crate::parse_with_options) returns.
+pub fn validate_with_options(json: &[u8], options: &ParseOptions) -> Result<(), Error> {
+    let mut values = json;
+    skip_document(&mut values, options).map_err(|e| e.locate(json, values))
+}
+
+/// Advances `values` past one JSON value and any whitespace before it, checking that the value
+/// is well-formed without allocating.
+/// # Errors
+/// If `values` does not start with a valid JSON value, with the same error
+/// [`parse`](crate::parse) would return, located relative to `values`.
+/// On error, `values` is left unchanged.
+pub fn skip_value(values: &mut &[u8]) -> Result<(), Error> {
+    skip_value_with_options(values, &ParseOptions::default())
+}
+
+/// Advances `values` past one JSON value according to `options`. See [`skip_value`].
+/// # Errors
+/// If `values` does not start with a valid JSON value or it does not fulfill `options`.
+pub fn skip_value_with_options(values: &mut &[u8], options: &ParseOptions) -> Result<(), Error> {
+    let mut remaining = *values;
+    skip_nested_value(&mut remaining, options, 0).map_err(|e| e.locate(values, remaining))?;
+    *values = remaining;
+    Ok(())
+}
+
+fn skip_document(values: &mut &[u8], options: &ParseOptions) -> Result<(), ErrorKind> {
+    skip_nested_value(values, options, 0)?;
+    skip_ignored(values, options)?;
+    if values.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorKind::InvalidEOF)
+    }
+}
+
+/// Skips a value nested in `depth` arrays or objects without allocating.
+/// Errors are the same as [`parse_value`](crate::parser::parse_value) would return.
+pub fn skip_nested_value(
+    values: &mut &[u8],
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<(), ErrorKind> {
+    skip_ignored(values, options)?;
+    let token = current_token(values)?;
+    match token {
+        b'{' => skip_object(values, options, depth),
+        b'[' => skip_array(values, options, depth),
+        b'"' => skip_string(values),
+        b'\'' if options.relaxed => skip_single_quoted(values),
+        b'n' => parse_null(values),
+        b't' => parse_true(values),
+        b'f' => parse_false(values),
+        b'0'..=b'9' | b'-' => parse_any_number(values, options).map(|_| ()),
+        b'+' | b'.' | b'I' | b'N' if options.relaxed => parse_relaxed_number(values).map(|_| ()),
+        other => Err(ErrorKind::InvalidToken(other)),
+    }
+}
+
+/// Skips a value that was already validated, e.g. by [`skip_nested_value`], together with
+/// `options`. Only strings, comments and the nesting of arrays and objects are tracked, which
+/// is faster than validating the value again.
+pub fn skip_validated_value(values: &mut &[u8], options: &ParseOptions) {
+    let mut depth = 0usize;
+    while let Some(byte) = values.first() {
+        match byte {
+            b'"' => skip_validated_string::<b'"'>(values),
+            // single quotes are only outside strings in the relaxed syntax
+            b'\'' => skip_validated_string::<b'\''>(values),
+            b'[' | b'{' => {
+                depth += 1;
+                *values = &values[1..];
+            }
+            b']' | b'}' => {
+                depth -= 1;
+                *values = &values[1..];
+            }
+            // a comment, only outside strings in the relaxed syntax
+            b'/' if depth > 0 => {
+                // comments are terminated in validated values
+                let _ = skip_ignored(values, options);
+            }
+            // a number or a literal, e.g. `true`
+            _ if depth == 0 => {
+                let length = values
+                    .iter()
+                    .position(|x| !(x.is_ascii_alphanumeric() || matches!(x, b'.' | b'+' | b'-')))
+                    .unwrap_or(values.len());
+                *values = &values[length..];
+            }
+            // whitespace, separators, numbers and literals in a container
+            _ => *values = &values[structural_run(values)..],
+        }
+        if depth == 0 {
+            return;
+        }
+    }
+}
+
+fn skip_array(values: &mut &[u8], options: &ParseOptions, depth: usize) -> Result<(), ErrorKind> {
+    for_each_item(values, options, depth, |values, _| {
+        skip_nested_value(values, options, depth + 1)
+    })
+}
+
+fn skip_object(values: &mut &[u8], options: &ParseOptions, depth: usize) -> Result<(), ErrorKind> {
+    for_each_entry(values, options, depth, skip_key, |values, _, _| {
+        skip_nested_value(values, options, depth + 1)
+    })
+}
+
+/// Consumes an array at `depth`, calling `item` with each item's index and the input
+/// starting at it. `item` must consume the item.
+pub fn for_each_item<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+    mut item: impl FnMut(&mut &'a [u8], usize) -> Result<(), ErrorKind>,
+) -> Result<(), ErrorKind> {
+    check_depth(options, depth)?;
+    *values = &values[1..];
+    let mut index = 0;
+    loop {
+        skip_ignored(values, options)?;
+        let token = current_token(values)?;
+        if token == b']' {
+            *values = &values[1..];
+            return Ok(());
+        };
+        if index > 0 {
+            if token != b',' {
+                return Err(ErrorKind::MissingComma(token));
+            }
+            *values = &values[1..]; // consume ","
+            if is_trailing_comma(values, b']', options)? {
+                return Ok(());
+            }
+        }
+
+        item(values, index)?;
+        index += 1;
+    }
+}
+
+/// Consumes an object at `depth`, reading each key with `key` and calling `entry` with the input
+/// starting at its value, the input starting at the key and what `key` returned. `entry` must
+/// consume the value.
+pub fn for_each_entry<'a, K>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+    key: impl Fn(&mut &'a [u8], &ParseOptions) -> Result<K, ErrorKind>,
+    mut entry: impl FnMut(&mut &'a [u8], &'a [u8], K) -> Result<(), ErrorKind>,
+) -> Result<(), ErrorKind> {
+    check_depth(options, depth)?;
+    *values = &values[1..];
+    let mut first = true;
+    loop {
+        skip_ignored(values, options)?;
+        let token = current_token(values)?;
+        if token == b'}' {
+            *values = &values[1..];
+            return Ok(());
+        };
+        if !first {
+            if token != b',' {
+                return Err(ErrorKind::MissingComma(token));
+            }
+            *values = &values[1..]; // consume ","
+            if is_trailing_comma(values, b'}', options)? {
+                return Ok(());
+            }
+            skip_ignored(values, options)?;
+        }
+        first = false;
+
+        let key_start = *values;
+        let key = key_and_colon(values, options, &key)?;
+        entry(values, key_start, key)?;
+    }
+}
+
+/// Reads an object's key with `key` and consumes the colon that follows it
+#[inline]
+fn key_and_colon<'a, K>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    key: impl Fn(&mut &'a [u8], &ParseOptions) -> Result<K, ErrorKind>,
+) -> Result<K, ErrorKind> {
+    let key = key(values, options)?;
+
+    skip_ignored(values, options)?;
+    let token = current_token(values)?;
+    if token != b':' {
+        return Err(ErrorKind::InvalidToken(token));
+    };
+    *values = &values[1..];
+    Ok(key)
+}
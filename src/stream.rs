@@ -0,0 +1,67 @@
+use core::ops::Range;
+
+use super::error::*;
+use super::options::ParseOptions;
+use super::parser::{parse_value, Value};
+use super::relaxed::skip_ignored;
+
+/// Iterator over concatenated JSON values (e.g. `{"a": 1}{"a": 2} 3`), returned by
+/// [`parse_stream`]. Each item contains the value and the byte span it occupies in the input.
+///
+/// The iterator stops after the first error.
+#[derive(Debug, Clone)]
+pub struct StreamIter<'a> {
+    json: &'a [u8],
+    remaining: &'a [u8],
+    options: ParseOptions,
+    finished: bool,
+}
+
+impl<'a> StreamIter<'a> {
+    /// The number of bytes of the input consumed so far.
+    pub fn byte_offset(&self) -> usize {
+        self.json.len() - self.remaining.len()
+    }
+}
+
+impl<'a> Iterator for StreamIter<'a> {
+    type Item = Result<(Value<'a>, Range<usize>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        if let Err(e) = skip_ignored(&mut self.remaining, &self.options) {
+            self.finished = true;
+            return Some(Err(e.locate(self.json, self.remaining)));
+        }
+        if self.remaining.is_empty() {
+            return None;
+        }
+
+        let start = self.byte_offset();
+        Some(match parse_value(&mut self.remaining, &self.options, 0) {
+            Ok(value) => Ok((value, start..self.byte_offset())),
+            Err(e) => {
+                self.finished = true;
+                Err(e.locate(self.json, self.remaining))
+            }
+        })
+    }
+}
+
+/// Parses a stream of JSON values that are concatenated or separated by whitespace.
+pub fn parse_stream(json: &[u8]) -> StreamIter<'_> {
+    parse_stream_with_options(json, ParseOptions::default())
+}
+
+/// Parses a stream of JSON values that are concatenated or separated by whitespace,
+/// according to `options`.
+pub fn parse_stream_with_options(json: &[u8], options: ParseOptions) -> StreamIter<'_> {
+    StreamIter {
+        json,
+        remaining: json,
+        options,
+        finished: false,
+    }
+}
@@ -2,16 +2,19 @@ use alloc::borrow::Cow;
 
 use alloc::string::String;
 
-use super::Error;
+use super::structural::string_run;
+use super::ErrorKind;
 
 #[inline]
-fn skip_escape(values: &mut &[u8]) -> Result<usize, Error> {
+fn skip_escape(values: &mut &[u8]) -> Result<usize, ErrorKind> {
     *values = &values[1..];
-    let ch = *values.get(0).ok_or(Error::InvalidEOF)?;
+    let ch = *values.first().ok_or(ErrorKind::InvalidEOF)?;
     if ch == b'u' {
         const NUM_UNICODE_CHARS: usize = 4;
-        if values.len() < NUM_UNICODE_CHARS {
-            return Err(Error::InvalidEOF);
+        // `values` starts at `u`, which must be followed by 4 chars and the next token
+        if values.len() <= NUM_UNICODE_CHARS {
+            *values = &values[values.len()..];
+            return Err(ErrorKind::InvalidEOF);
         } else {
             *values = &values[NUM_UNICODE_CHARS..];
         }
@@ -22,63 +25,159 @@ fn skip_escape(values: &mut &[u8]) -> Result<usize, Error> {
 }
 
 #[inline]
-fn compute_length(values: &mut &[u8]) -> Result<(usize, usize, usize), Error> {
+fn compute_length<const QUOTE: u8>(values: &mut &[u8]) -> Result<(usize, usize, usize), ErrorKind> {
     let mut length = 0;
     let mut escapes = 0;
     let mut controls = 0;
     debug_assert!(!values.is_empty(), "Tried to parse string on empty input");
     loop {
         *values = &values[1..];
-        let ch = *values.get(0).ok_or(Error::InvalidEOF)?;
+        // skip the bytes that need no handling, many at a time
+        let run = string_run::<QUOTE>(values);
+        *values = &values[run..];
+        length += run;
+        let ch = *values.first().ok_or(ErrorKind::InvalidEOF)?;
         length += 1;
         match ch {
             b'\\' => {
                 escapes += 1;
                 length += skip_escape(values)?;
-            },
-            b'"' => {
+            }
+            ch if ch == QUOTE => {
                 *values = &values[1..];
                 return Ok((length, escapes, controls));
             }
-            _ if ch.is_ascii_control() => {
+            // JSON allows every character but control characters U+0000 through U+001F
+            0..=0x1f => {
                 controls += 1;
-            },
+            }
             _ => {}
         }
     }
 }
 
+/// Validates a string delimited by `QUOTE`, returning its contents (without quotes) and its
+/// number of escapes. On error, `values` points to where the error was found.
 #[inline]
-pub fn parse_string<'b, 'a>(values: &'b mut &'a [u8]) -> Result<Cow<'a, str>, Error> {
-    // compute the size of the string value and whether it has escapes
+fn validate<'a, const QUOTE: u8>(values: &mut &'a [u8]) -> Result<(&'a [u8], usize), ErrorKind> {
     let string = *values;
-    let (length, escapes, controls) = compute_length(values)?;
+    let (length, escapes, controls) = compute_length::<QUOTE>(values)?;
 
-    let mut data = &string[1..length];
+    let all = &string[1..length];
     if controls > 0 {
-        Err(Error::StringWithControlCharacters)
-    } else if escapes > 0 {
+        let position = all.iter().position(|x| *x < 0x20).unwrap();
+        *values = &string[1 + position..];
+        Err(ErrorKind::StringWithControlCharacters)
+    } else {
+        Ok((all, escapes))
+    }
+}
+
+/// Parses a JSON string. On error, `values` points to where the error was found.
+#[inline]
+pub fn parse_string<'a>(values: &mut &'a [u8]) -> Result<Cow<'a, str>, ErrorKind> {
+    parse_quoted::<b'"'>(values)
+}
+
+/// Parses a single-quoted string, where `\'` escapes a single quote.
+#[inline]
+pub fn parse_single_quoted<'a>(values: &mut &'a [u8]) -> Result<Cow<'a, str>, ErrorKind> {
+    parse_quoted::<b'\''>(values)
+}
+
+#[inline]
+fn parse_quoted<'a, const QUOTE: u8>(values: &mut &'a [u8]) -> Result<Cow<'a, str>, ErrorKind> {
+    let string = *values;
+    let (all, escapes) = validate::<QUOTE>(values)?;
+
+    let mut data = all;
+    if escapes > 0 {
         let capacity = data.len() - escapes;
         let mut container = String::with_capacity(capacity);
 
-        while !data.is_empty() {
-            let first = data[0];
-            if first == b'\\' {
-                data = &data[1..];
-                data = parse_escape(data, &mut container)?;
-            } else {
-                container.push(first as char);
-                data = &data[1..];
-            }
-        }
+        unescape::<QUOTE>(&mut data, |x| container.push_str(x))
+            .inspect_err(|_| *values = &string[1 + all.len() - data.len()..])?;
         Ok(Cow::Owned(container))
     } else {
-        alloc::str::from_utf8(data)
-            .map(Cow::Borrowed)
-            .map_err(|_| Error::InvalidUtf8)
+        alloc::str::from_utf8(data).map(Cow::Borrowed).map_err(|e| {
+            *values = &string[1 + e.valid_up_to()..];
+            ErrorKind::InvalidUtf8
+        })
     }
 }
 
+/// Skips a JSON string without allocating, erroring like [`parse_string`].
+#[inline]
+pub fn skip_string(values: &mut &[u8]) -> Result<(), ErrorKind> {
+    skip_quoted::<b'"'>(values)
+}
+
+/// Skips a single-quoted string without allocating, erroring like [`parse_single_quoted`].
+#[inline]
+pub fn skip_single_quoted(values: &mut &[u8]) -> Result<(), ErrorKind> {
+    skip_quoted::<b'\''>(values)
+}
+
+/// Skips a string delimited by `QUOTE` that was already validated, without validating it again
+#[inline]
+pub fn skip_validated_string<const QUOTE: u8>(values: &mut &[u8]) {
+    // skip the opening quote
+    let mut rest = &values[1..];
+    loop {
+        rest = &rest[string_run::<QUOTE>(rest)..];
+        match rest {
+            // the escaped character does not end the string, even if it is a quote
+            [b'\\', _, tail @ ..] => rest = tail,
+            [_, tail @ ..] => {
+                *values = tail;
+                return;
+            }
+            [] => {
+                *values = rest;
+                return;
+            }
+        }
+    }
+}
+
+#[inline]
+fn skip_quoted<const QUOTE: u8>(values: &mut &[u8]) -> Result<(), ErrorKind> {
+    let string = *values;
+    let (all, _) = validate::<QUOTE>(values)?;
+
+    let mut data = all;
+    unescape::<QUOTE>(&mut data, |_| {})
+        .inspect_err(|_| *values = &string[1 + all.len() - data.len()..])
+}
+
+/// Unescapes `data`, the contents of a string with escapes, passing each run of UTF-8
+/// between escapes and each unescaped character to `push`.
+/// On error, `data` points to where the error was found.
+#[inline]
+fn unescape<const QUOTE: u8>(
+    data: &mut &[u8],
+    mut push: impl FnMut(&str),
+) -> Result<(), ErrorKind> {
+    while !data.is_empty() {
+        // validated contents have no unescaped quotes nor control characters
+        let end = string_run::<QUOTE>(data);
+        let run = alloc::str::from_utf8(&data[..end]).map_err(|e| {
+            *data = &data[e.valid_up_to()..];
+            ErrorKind::InvalidUtf8
+        })?;
+        push(run);
+        *data = &data[end..];
+
+        if !data.is_empty() {
+            // `data` starts with a backslash
+            let (ch, rest) = parse_escape::<QUOTE>(&data[1..])?;
+            push(ch.encode_utf8(&mut [0; 4]));
+            *data = rest;
+        }
+    }
+    Ok(())
+}
+
 #[allow(clippy::zero_prefixed_literal)]
 static HEX: [u8; 256] = {
     const __: u8 = 255; // not a hex digit
@@ -113,27 +212,28 @@ fn decode_hex_val(val: u8) -> Option<u16> {
     }
 }
 
-/// Parses a JSON escape sequence and appends it into the scratch space. Assumes
-/// the previous byte read was a backslash.
-fn parse_escape<'a>(mut input: &'a [u8], scratch: &mut String) -> Result<&'a [u8], Error> {
+/// Parses a JSON escape sequence of a string delimited by `QUOTE`, returning the escaped char
+/// and the remaining input. Assumes the previous byte read was a backslash.
+fn parse_escape<const QUOTE: u8>(mut input: &[u8]) -> Result<(char, &[u8]), ErrorKind> {
     let ch = input[0];
     input = &input[1..];
-    match ch {
-        b'"' => scratch.push('"'),
-        b'\\' => scratch.push('\\'),
-        b'/' => scratch.push('/'),
-        b'b' => scratch.push('\x08'),
-        b'f' => scratch.push('\x0c'),
-        b'n' => scratch.push('\n'),
-        b'r' => scratch.push('\r'),
-        b't' => scratch.push('\t'),
+    let c = match ch {
+        b'"' => '"',
+        b'\'' if QUOTE == b'\'' => '\'',
+        b'\\' => '\\',
+        b'/' => '/',
+        b'b' => '\x08',
+        b'f' => '\x0c',
+        b'n' => '\n',
+        b'r' => '\r',
+        b't' => '\t',
         b'u' => {
             let hex = decode_hex_escape(input)?;
             input = &input[4..];
 
-            let c = match hex {
+            match hex {
                 n @ 0xDC00..=0xDFFF => {
-                    return Err(Error::InvalidLoneLeadingSurrogateInHexEscape(n))
+                    return Err(ErrorKind::InvalidLoneLeadingSurrogateInHexEscape(n))
                 }
 
                 // Non-BMP characters are encoded as a sequence of two hex
@@ -141,48 +241,48 @@ fn parse_escape<'a>(mut input: &'a [u8], scratch: &mut String) -> Result<&'a [u8
                 // utf-8 string the surrogates are required to be paired,
                 // whereas deserializing a byte string accepts lone surrogates.
                 n1 @ 0xD800..=0xDBFF => {
-                    let byte = input.get(0).ok_or(Error::InvalidEOF)?;
+                    let byte = input.first().ok_or(ErrorKind::InvalidEOF)?;
                     if *byte == b'\\' {
                         input = &input[1..];
                     } else {
-                        return Err(Error::UnexpectedEndOfHexEscape);
+                        return Err(ErrorKind::UnexpectedEndOfHexEscape);
                     }
 
-                    let byte = input.get(0).ok_or(Error::InvalidEOF)?;
+                    // the leading surrogate must be followed by a trailing one, not by any other
+                    // escape, e.g. `\n`
+                    let byte = input.first().ok_or(ErrorKind::InvalidEOF)?;
                     if *byte == b'u' {
                         input = &input[1..];
                     } else {
-                        return parse_escape(input, scratch);
+                        return Err(ErrorKind::UnexpectedEndOfHexEscape);
                     }
 
                     let n2 = decode_hex_escape(input)?;
                     input = &input[4..];
                     if !(0xDC00..=0xDFFF).contains(&n2) {
-                        return Err(Error::InvalidSurrogateInHexEscape(n2));
+                        return Err(ErrorKind::InvalidSurrogateInHexEscape(n2));
                     }
 
                     let n = (((n1 - 0xD800) as u32) << 10 | (n2 - 0xDC00) as u32) + 0x1_0000;
-                    char::from_u32(n as u32).unwrap()
+                    char::from_u32(n).unwrap()
                 }
 
                 // Every u16 outside of the surrogate ranges above is guaranteed
                 // to be a legal char.
                 n => char::from_u32(n as u32).unwrap(),
-            };
-
-            scratch.push(c);
+            }
         }
-        other => return Err(Error::InvalidEscaped(other)),
-    }
+        other => return Err(ErrorKind::InvalidEscaped(other)),
+    };
 
-    Ok(input)
+    Ok((c, input))
 }
 
-fn decode_hex_escape(input: &[u8]) -> Result<u16, Error> {
+fn decode_hex_escape(input: &[u8]) -> Result<u16, ErrorKind> {
     let numbers_u8: [u8; 4] = input[..4].try_into().unwrap();
     let mut n = 0;
     for number in numbers_u8 {
-        let hex = decode_hex_val(number).ok_or(Error::InvalidHex(number))?;
+        let hex = decode_hex_val(number).ok_or(ErrorKind::InvalidHex(number))?;
         n = (n << 4) + hex;
     }
     Ok(n)
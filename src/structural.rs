@@ -0,0 +1,318 @@
+// Finds the end of runs of string contents, of whitespace and of bytes between structural
+// characters by classifying `BLOCK` bytes at a time, either with portable SWAR (SIMD within a
+// register) on `u64`s or, with the `simd` feature, with `core::arch` on x86 (16 bytes with SSE2,
+// 32 with AVX2).
+//
+// This is block scanning on demand: each call scans from the position of the parser. Unlike
+// simdjson's stage 1, no index of the structural characters of the input is built ahead of
+// parsing.
+
+#[cfg(not(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+use swar as backend;
+
+#[cfg(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+use arch as backend;
+
+use backend::BLOCK;
+
+/// Whether `byte` ends a run of contents of a string delimited by `QUOTE`
+#[inline]
+fn is_string_stop<const QUOTE: u8>(byte: u8) -> bool {
+    // JSON allows every character but control characters U+0000 through U+001F
+    byte == QUOTE || byte == b'\\' || byte < 0x20
+}
+
+#[inline]
+fn is_whitespace(byte: u8) -> bool {
+    matches!(byte, b'\n' | b' ' | b'\r' | b'\t')
+}
+
+/// Whether `byte` opens or closes an array, object or string, or starts a comment
+#[inline]
+fn is_structural(byte: u8) -> bool {
+    matches!(byte, b'[' | b']' | b'{' | b'}' | b'"' | b'\'' | b'/')
+}
+
+/// Returns the number of leading bytes of `bytes` that are neither `QUOTE`, a backslash,
+/// nor a control character
+#[inline]
+pub fn string_run<const QUOTE: u8>(bytes: &[u8]) -> usize {
+    find(
+        bytes,
+        backend::string_stop::<QUOTE>,
+        is_string_stop::<QUOTE>,
+    )
+}
+
+/// Returns the number of leading bytes of `bytes` that are whitespace, i.e. the offset of the
+/// next structural character or value
+#[inline]
+pub fn whitespace_run(bytes: &[u8]) -> usize {
+    // whitespace is mostly absent or a single space, e.g. `, ` and `: `
+    match bytes {
+        [first, ..] if !is_whitespace(*first) => 0,
+        [_, second, ..] if !is_whitespace(*second) => 1,
+        _ => long_whitespace_run(bytes),
+    }
+}
+
+#[inline]
+fn long_whitespace_run(bytes: &[u8]) -> usize {
+    find(bytes, backend::non_whitespace, |x| !is_whitespace(x))
+}
+
+/// Returns the number of leading bytes of `bytes` that neither open nor close an array, object
+/// or string, nor start a comment, i.e. that skipping a validated value passes over
+#[inline]
+pub fn structural_run(bytes: &[u8]) -> usize {
+    find(bytes, backend::structural, is_structural)
+}
+
+/// Returns the position of the first byte of `bytes` matching `block` (for whole blocks) and
+/// `byte` (for the remaining bytes), or the length of `bytes` if none does
+#[inline(always)]
+fn find(
+    bytes: &[u8],
+    block: impl Fn(&[u8; BLOCK]) -> Option<usize>,
+    byte: impl Fn(u8) -> bool,
+) -> usize {
+    let mut chunks = bytes.chunks_exact(BLOCK);
+    let mut offset = 0;
+    for chunk in &mut chunks {
+        if let Some(position) = block(chunk.try_into().unwrap()) {
+            return offset + position;
+        }
+        offset += BLOCK;
+    }
+    let remainder = chunks.remainder();
+    offset
+        + remainder
+            .iter()
+            .position(|x| byte(*x))
+            .unwrap_or(remainder.len())
+}
+
+#[cfg(not(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+)))]
+mod swar {
+    pub const BLOCK: usize = 16;
+
+    const ONES: u64 = 0x0101_0101_0101_0101;
+    const LOW: u64 = ONES * 0x7f;
+    const HIGH: u64 = ONES * 0x80;
+
+    /// Sets the high bit of each byte of `word` that is zero
+    #[inline]
+    fn zeros(word: u64) -> u64 {
+        // the sum does not carry across bytes
+        !(((word & LOW) + LOW) | word | LOW)
+    }
+
+    /// Sets the high bit of each byte of `word` equal to `byte`
+    #[inline]
+    fn equal(word: u64, byte: u8) -> u64 {
+        zeros(word ^ (ONES * byte as u64))
+    }
+
+    /// Sets the high bit of each byte of `word` that is less than `n`, for `n <= 128`
+    #[inline]
+    fn less_than(word: u64, n: u8) -> u64 {
+        !(((word & LOW) + ONES * (0x80 - n as u64)) | word) & HIGH
+    }
+
+    /// Returns the position of the first byte of `block` whose high bit is set by `mask`
+    #[inline(always)]
+    fn first(block: &[u8; BLOCK], mask: impl Fn(u64) -> u64) -> Option<usize> {
+        block.chunks_exact(8).enumerate().find_map(|(i, word)| {
+            let mask = mask(u64::from_le_bytes(word.try_into().unwrap()));
+            (mask != 0).then(|| i * 8 + (mask.trailing_zeros() / 8) as usize)
+        })
+    }
+
+    #[inline]
+    pub fn string_stop<const QUOTE: u8>(block: &[u8; BLOCK]) -> Option<usize> {
+        first(block, |word| {
+            equal(word, QUOTE) | equal(word, b'\\') | less_than(word, 0x20)
+        })
+    }
+
+    #[inline]
+    pub fn structural(block: &[u8; BLOCK]) -> Option<usize> {
+        first(block, |word| {
+            equal(word, b'[')
+                | equal(word, b']')
+                | equal(word, b'{')
+                | equal(word, b'}')
+                | equal(word, b'"')
+                | equal(word, b'\'')
+                | equal(word, b'/')
+        })
+    }
+
+    #[inline]
+    pub fn non_whitespace(block: &[u8; BLOCK]) -> Option<usize> {
+        first(block, |word| {
+            let whitespace =
+                equal(word, b' ') | equal(word, b'\n') | equal(word, b'\r') | equal(word, b'\t');
+            !whitespace & HIGH
+        })
+    }
+}
+
+#[cfg(all(
+    feature = "simd",
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse2"
+))]
+#[allow(unsafe_code)]
+mod arch {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    #[cfg(not(target_feature = "avx2"))]
+    mod lanes {
+        use super::*;
+
+        pub const BLOCK: usize = 16;
+        pub type Vector = __m128i;
+
+        #[inline]
+        pub fn load(block: &[u8; BLOCK]) -> Vector {
+            // SAFETY: SSE2 is enabled at compile time (`target_feature = "sse2"`), `block` has
+            // `BLOCK` bytes and unaligned loads have no alignment requirement
+            unsafe { _mm_loadu_si128(block.as_ptr().cast()) }
+        }
+
+        #[inline]
+        pub fn splat(byte: u8) -> Vector {
+            // SAFETY: SSE2 is enabled at compile time (`target_feature = "sse2"`)
+            unsafe { _mm_set1_epi8(byte as i8) }
+        }
+
+        #[inline]
+        pub fn equal(a: Vector, b: Vector) -> Vector {
+            // SAFETY: SSE2 is enabled at compile time (`target_feature = "sse2"`)
+            unsafe { _mm_cmpeq_epi8(a, b) }
+        }
+
+        #[inline]
+        pub fn or(a: Vector, b: Vector) -> Vector {
+            // SAFETY: SSE2 is enabled at compile time (`target_feature = "sse2"`)
+            unsafe { _mm_or_si128(a, b) }
+        }
+
+        #[inline]
+        pub fn min(a: Vector, b: Vector) -> Vector {
+            // SAFETY: SSE2 is enabled at compile time (`target_feature = "sse2"`)
+            unsafe { _mm_min_epu8(a, b) }
+        }
+
+        #[inline]
+        pub fn mask(a: Vector) -> u32 {
+            // SAFETY: SSE2 is enabled at compile time (`target_feature = "sse2"`)
+            unsafe { _mm_movemask_epi8(a) as u32 }
+        }
+    }
+
+    #[cfg(target_feature = "avx2")]
+    mod lanes {
+        use super::*;
+
+        pub const BLOCK: usize = 32;
+        pub type Vector = __m256i;
+
+        #[inline]
+        pub fn load(block: &[u8; BLOCK]) -> Vector {
+            // SAFETY: AVX2 is enabled at compile time (`target_feature = "avx2"`), `block` has
+            // `BLOCK` bytes and unaligned loads have no alignment requirement
+            unsafe { _mm256_loadu_si256(block.as_ptr().cast()) }
+        }
+
+        #[inline]
+        pub fn splat(byte: u8) -> Vector {
+            // SAFETY: AVX2 is enabled at compile time (`target_feature = "avx2"`)
+            unsafe { _mm256_set1_epi8(byte as i8) }
+        }
+
+        #[inline]
+        pub fn equal(a: Vector, b: Vector) -> Vector {
+            // SAFETY: AVX2 is enabled at compile time (`target_feature = "avx2"`)
+            unsafe { _mm256_cmpeq_epi8(a, b) }
+        }
+
+        #[inline]
+        pub fn or(a: Vector, b: Vector) -> Vector {
+            // SAFETY: AVX2 is enabled at compile time (`target_feature = "avx2"`)
+            unsafe { _mm256_or_si256(a, b) }
+        }
+
+        #[inline]
+        pub fn min(a: Vector, b: Vector) -> Vector {
+            // SAFETY: AVX2 is enabled at compile time (`target_feature = "avx2"`)
+            unsafe { _mm256_min_epu8(a, b) }
+        }
+
+        #[inline]
+        pub fn mask(a: Vector) -> u32 {
+            // SAFETY: AVX2 is enabled at compile time (`target_feature = "avx2"`)
+            unsafe { _mm256_movemask_epi8(a) as u32 }
+        }
+    }
+
+    pub use lanes::BLOCK;
+    use lanes::*;
+
+    #[inline]
+    fn first(mask: u32) -> Option<usize> {
+        (mask != 0).then(|| mask.trailing_zeros() as usize)
+    }
+
+    #[inline]
+    pub fn string_stop<const QUOTE: u8>(block: &[u8; BLOCK]) -> Option<usize> {
+        let bytes = load(block);
+        // bytes up to 0x1f are those equal to their minimum with 0x1f
+        let controls = equal(min(bytes, splat(0x1f)), bytes);
+        let stops = or(
+            or(equal(bytes, splat(QUOTE)), equal(bytes, splat(b'\\'))),
+            controls,
+        );
+        first(mask(stops))
+    }
+
+    #[inline]
+    pub fn non_whitespace(block: &[u8; BLOCK]) -> Option<usize> {
+        let bytes = load(block);
+        let whitespace = or(
+            or(equal(bytes, splat(b' ')), equal(bytes, splat(b'\n'))),
+            or(equal(bytes, splat(b'\r')), equal(bytes, splat(b'\t'))),
+        );
+        first(!mask(whitespace) & (u32::MAX >> (32 - BLOCK)))
+    }
+
+    #[inline]
+    pub fn structural(block: &[u8; BLOCK]) -> Option<usize> {
+        let bytes = load(block);
+        let brackets = or(
+            or(equal(bytes, splat(b'[')), equal(bytes, splat(b']'))),
+            or(equal(bytes, splat(b'{')), equal(bytes, splat(b'}'))),
+        );
+        let others = or(
+            or(equal(bytes, splat(b'"')), equal(bytes, splat(b'\''))),
+            equal(bytes, splat(b'/')),
+        );
+        first(mask(or(brackets, others)))
+    }
+}
@@ -0,0 +1,702 @@
+use alloc::borrow::Cow;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::{
+    boolean::{parse_false, parse_true},
+    null::parse_null,
+    object::insert,
+    parser::{check_depth, current_token},
+    relaxed::{is_number_start, is_trailing_comma, parse_any_number, parse_key, skip_ignored},
+    string::{parse_single_quoted, parse_string},
+    DuplicateKeys, Number, Object, ParseOptions, Value,
+};
+
+use super::error::*;
+
+// The words of a tape, in the style of simdjson: the 8 high bits of the first word of an entry
+// are its tag and the 56 low bits its payload. Owned strings and keys, numbers and the starts
+// of arrays and objects have a second word.
+//
+// | entry            | payload                                            | second word         |
+// |------------------|----------------------------------------------------|---------------------|
+// | null, true/false | 0                                                  |                     |
+// | string, key      | the index of its text in `Tape::strings`           |                     |
+// | owned string/key | `OWNED` and its offset in `Tape::owned`            | its length          |
+// | integer, float   | the offset of its text in the input, or with       | its length          |
+// |                  | `OWNED` the offset of its text in `Tape::owned`    |                     |
+// | start            | the position of its end                            | its number of items |
+// | end              | the position of its start                          |                     |
+const NULL: u8 = b'n';
+const TRUE: u8 = b't';
+const FALSE: u8 = b'f';
+const STRING: u8 = b'"';
+const KEY: u8 = b':';
+const INTEGER: u8 = b'i';
+const FLOAT: u8 = b'd';
+const START_ARRAY: u8 = b'[';
+const END_ARRAY: u8 = b']';
+const START_OBJECT: u8 = b'{';
+const END_OBJECT: u8 = b'}';
+
+const PAYLOAD: u64 = (1 << 56) - 1;
+const OWNED: u64 = 1 << 55;
+
+#[inline]
+fn word(tag: u8, payload: u64) -> u64 {
+    (tag as u64) << 56 | payload
+}
+
+#[inline]
+fn tag(word: u64) -> u8 {
+    (word >> 56) as u8
+}
+
+#[inline]
+fn payload(word: u64) -> usize {
+    (word & PAYLOAD) as usize
+}
+
+/// The number of words of the entry whose first word is `word`
+#[inline]
+fn width(word: u64) -> usize {
+    match tag(word) {
+        NULL | TRUE | FALSE | END_ARRAY | END_OBJECT => 1,
+        STRING | KEY if word & OWNED == 0 => 1,
+        _ => 2,
+    }
+}
+
+/// Splits the text of a number at its exponent, e.g. `1.5e3` into `1.5` and `3`
+fn to_number(float: bool, text: Cow<'_, [u8]>) -> Number<'_> {
+    let split = |text: &[u8]| match text.iter().position(|x| matches!(x, b'e' | b'E')) {
+        Some(e) => e,
+        None => text.len(),
+    };
+    let (mantissa, exponent): (Cow<_>, Cow<_>) = match text {
+        Cow::Borrowed(text) => {
+            let e = split(text);
+            (
+                text[..e].into(),
+                text.get(e + 1..).unwrap_or_default().into(),
+            )
+        }
+        Cow::Owned(text) => {
+            let e = split(&text);
+            (
+                text[..e].to_vec().into(),
+                text.get(e + 1..).unwrap_or_default().to_vec().into(),
+            )
+        }
+    };
+    if float {
+        Number::Float(mantissa, exponent)
+    } else {
+        Number::Integer(mantissa, exponent)
+    }
+}
+
+/// An entry of a [`Tape`], decoded from its words. Positions are in words of the tape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TapeEntry<'t> {
+    /// A `null`
+    Null,
+    /// A bool
+    Bool(bool),
+    /// A number
+    Number(Number<'t>),
+    /// A string
+    String(&'t str),
+    /// The key of an object, followed by the entries of its value
+    Key(&'t str),
+    /// The start of an array (`[`), with its number of items and the position of its
+    /// [`TapeEntry::EndArray`]
+    StartArray {
+        /// The number of items
+        len: usize,
+        /// The position of the matching [`TapeEntry::EndArray`]
+        end: usize,
+    },
+    /// The end of an array (`]`)
+    EndArray {
+        /// The position of the matching [`TapeEntry::StartArray`]
+        start: usize,
+    },
+    /// The start of an object (`{`), with its number of keys and the position of its
+    /// [`TapeEntry::EndObject`]
+    StartObject {
+        /// The number of keys, including repeated ones
+        len: usize,
+        /// The position of the matching [`TapeEntry::EndObject`]
+        end: usize,
+    },
+    /// The end of an object (`}`)
+    EndObject {
+        /// The position of the matching [`TapeEntry::StartObject`]
+        start: usize,
+    },
+}
+
+/// A JSON document parsed into one flat vector of `u64` words, returned by [`parse_tape`].
+///
+/// Unlike [`Value`], which allocates every array and object, a tape only allocates its words,
+/// the strings borrowed from the input and a buffer of the strings and numbers that differ from
+/// the input, e.g. unescaped strings.
+/// Each entry is one or two words, and arrays and objects are delimited by start and end
+/// entries that refer to each other, so that they can be skipped without visiting their items.
+/// Objects keep all their keys in order, also repeated ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tape<'a> {
+    json: &'a [u8],
+    // the strings borrowed from the input, validated while parsing
+    strings: Vec<&'a str>,
+    words: Vec<u64>,
+    // the text of entries with `OWNED`
+    owned: String,
+    duplicate_keys: DuplicateKeys,
+}
+
+impl<'a> Tape<'a> {
+    /// The entries of this tape, starting with the root value
+    pub fn entries(&self) -> TapeEntries<'_, 'a> {
+        TapeEntries {
+            tape: self,
+            index: 0,
+            end: self.words.len(),
+        }
+    }
+
+    /// The root value of this tape
+    pub fn root(&self) -> TapeValue<'_, 'a> {
+        TapeValue {
+            tape: self,
+            index: 0,
+        }
+    }
+
+    /// Returns whether the text of the entry at `index`, of two words, is owned and its range
+    #[inline]
+    fn span(&self, index: usize) -> (bool, Range<usize>) {
+        let word = self.words[index];
+        let start = payload(word & !OWNED);
+        let len = self.words[index + 1] as usize;
+        (word & OWNED != 0, start..start + len)
+    }
+
+    fn str(&self, index: usize) -> &str {
+        let word = self.words[index];
+        if word & OWNED == 0 {
+            self.strings[payload(word)]
+        } else {
+            &self.owned[self.span(index).1]
+        }
+    }
+
+    fn cow_str(&self, index: usize) -> Cow<'a, str> {
+        let word = self.words[index];
+        if word & OWNED == 0 {
+            Cow::Borrowed(self.strings[payload(word)])
+        } else {
+            Cow::Owned(self.owned[self.span(index).1].into())
+        }
+    }
+
+    fn bytes(&self, index: usize) -> &[u8] {
+        match self.span(index) {
+            (true, range) => &self.owned.as_bytes()[range],
+            (false, range) => &self.json[range],
+        }
+    }
+
+    fn cow_bytes(&self, index: usize) -> Cow<'a, [u8]> {
+        match self.span(index) {
+            (true, range) => Cow::Owned(self.owned.as_bytes()[range].into()),
+            (false, range) => Cow::Borrowed(&self.json[range]),
+        }
+    }
+
+    /// Decodes the entry at `index`
+    fn entry(&self, index: usize) -> TapeEntry<'_> {
+        let word = self.words[index];
+        match tag(word) {
+            NULL => TapeEntry::Null,
+            TRUE => TapeEntry::Bool(true),
+            FALSE => TapeEntry::Bool(false),
+            STRING => TapeEntry::String(self.str(index)),
+            KEY => TapeEntry::Key(self.str(index)),
+            tag @ (INTEGER | FLOAT) => {
+                TapeEntry::Number(to_number(tag == FLOAT, self.bytes(index).into()))
+            }
+            START_ARRAY => TapeEntry::StartArray {
+                len: self.words[index + 1] as usize,
+                end: payload(word),
+            },
+            END_ARRAY => TapeEntry::EndArray {
+                start: payload(word),
+            },
+            START_OBJECT => TapeEntry::StartObject {
+                len: self.words[index + 1] as usize,
+                end: payload(word),
+            },
+            END_OBJECT => TapeEntry::EndObject {
+                start: payload(word),
+            },
+            _ => unreachable!("the tape only contains known tags"),
+        }
+    }
+}
+
+/// Iterator over entries of a [`Tape`], returned by [`Tape::entries`] and [`TapeValue::entries`]
+#[derive(Debug, Clone)]
+pub struct TapeEntries<'t, 'a> {
+    tape: &'t Tape<'a>,
+    index: usize,
+    // the position after the last entry
+    end: usize,
+}
+
+impl<'t, 'a> Iterator for TapeEntries<'t, 'a> {
+    type Item = TapeEntry<'t>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        let entry = self.tape.entry(self.index);
+        self.index += width(self.tape.words[self.index]);
+        Some(entry)
+    }
+}
+
+/// A value of a [`Tape`], to navigate it
+#[derive(Debug, Clone, Copy)]
+pub struct TapeValue<'t, 'a> {
+    tape: &'t Tape<'a>,
+    index: usize,
+}
+
+impl<'t, 'a> TapeValue<'t, 'a> {
+    /// The first entry of this value
+    pub fn entry(&self) -> TapeEntry<'t> {
+        self.tape.entry(self.index)
+    }
+
+    /// The entries of this value: one for scalars, and from start to end for arrays and objects
+    pub fn entries(&self) -> TapeEntries<'t, 'a> {
+        TapeEntries {
+            tape: self.tape,
+            index: self.index,
+            end: self.next(),
+        }
+    }
+
+    #[inline]
+    fn word(&self) -> u64 {
+        self.tape.words[self.index]
+    }
+
+    /// The position of the entry after this value
+    #[inline]
+    fn next(&self) -> usize {
+        let word = self.word();
+        match tag(word) {
+            START_ARRAY | START_OBJECT => payload(word) + 1,
+            _ => self.index + width(word),
+        }
+    }
+
+    fn at(&self, index: usize) -> Self {
+        Self {
+            tape: self.tape,
+            index,
+        }
+    }
+
+    /// Returns the value of `key` if this is an object that contains it.
+    /// The first value wins when the key is repeated with [`DuplicateKeys::FirstWins`]
+    /// and the last one otherwise.
+    pub fn get(&self, key: &str) -> Option<TapeValue<'t, 'a>> {
+        let mut found = None;
+        for (k, value) in self.iter_object()? {
+            if k == key {
+                found = Some(value);
+                if self.tape.duplicate_keys == DuplicateKeys::FirstWins {
+                    break;
+                }
+            }
+        }
+        found
+    }
+
+    /// Returns the item at `index` if this is an array with more than `index` items
+    pub fn index(&self, index: usize) -> Option<TapeValue<'t, 'a>> {
+        self.iter_array()?.nth(index)
+    }
+
+    /// Returns an iterator over the items of this value if it is an array
+    pub fn iter_array(&self) -> Option<TapeArrayIter<'t, 'a>> {
+        let word = self.word();
+        (tag(word) == START_ARRAY).then(|| TapeArrayIter {
+            next: self.at(self.index + 2),
+            end: payload(word),
+        })
+    }
+
+    /// Returns an iterator over the keys and values of this value if it is an object
+    pub fn iter_object(&self) -> Option<TapeObjectIter<'t, 'a>> {
+        let word = self.word();
+        (tag(word) == START_OBJECT).then(|| TapeObjectIter {
+            next: self.at(self.index + 2),
+            end: payload(word),
+        })
+    }
+
+    /// Converts this value into a [`Value`], applying the [`DuplicateKeys`] policy the tape was
+    /// parsed with to its objects
+    pub fn to_value(&self) -> Value<'a> {
+        let tape = self.tape;
+        match tag(self.word()) {
+            NULL => Value::Null,
+            TRUE => Value::Bool(true),
+            FALSE => Value::Bool(false),
+            STRING => Value::String(tape.cow_str(self.index)),
+            tag @ (INTEGER | FLOAT) => {
+                Value::Number(to_number(tag == FLOAT, tape.cow_bytes(self.index)))
+            }
+            START_ARRAY => {
+                let mut items = Vec::with_capacity(tape.words[self.index + 1] as usize);
+                items.extend(
+                    self.iter_array()
+                        .into_iter()
+                        .flatten()
+                        .map(|x| x.to_value()),
+                );
+                Value::Array(items)
+            }
+            START_OBJECT => {
+                let mut iter = self.iter_object().unwrap();
+                let entries = core::iter::from_fn(|| iter.next_entry())
+                    .map(|(key, value)| (tape.cow_str(key), value.to_value()));
+                match tape.duplicate_keys {
+                    DuplicateKeys::KeepAll => Value::MultiObject(entries.collect()),
+                    // repeated keys were rejected when parsing with `DuplicateKeys::Error`
+                    DuplicateKeys::Error => {
+                        Value::Object(to_object(entries, DuplicateKeys::LastWins))
+                    }
+                    policy => Value::Object(to_object(entries, policy)),
+                }
+            }
+            _ => unreachable!("values do not start with keys or ends"),
+        }
+    }
+}
+
+fn to_object<'a>(
+    entries: impl Iterator<Item = (Cow<'a, str>, Value<'a>)>,
+    policy: DuplicateKeys,
+) -> Object<'a> {
+    let mut object = Object::new();
+    for (key, value) in entries {
+        // `LastWins` and `FirstWins` do not error
+        let _ = insert(&mut object, key, value, policy);
+    }
+    object
+}
+
+/// Iterator over the items of an array of a [`Tape`], returned by [`TapeValue::iter_array`]
+#[derive(Debug, Clone)]
+pub struct TapeArrayIter<'t, 'a> {
+    next: TapeValue<'t, 'a>,
+    // the position of the end of the array
+    end: usize,
+}
+
+impl<'t, 'a> Iterator for TapeArrayIter<'t, 'a> {
+    type Item = TapeValue<'t, 'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.index == self.end {
+            return None;
+        }
+        let item = self.next;
+        self.next = item.at(item.next());
+        Some(item)
+    }
+}
+
+/// Iterator over the keys and values of an object of a [`Tape`], returned by
+/// [`TapeValue::iter_object`]
+#[derive(Debug, Clone)]
+pub struct TapeObjectIter<'t, 'a> {
+    next: TapeValue<'t, 'a>,
+    // the position of the end of the object
+    end: usize,
+}
+
+impl<'t, 'a> TapeObjectIter<'t, 'a> {
+    /// Returns the position of the next key and its value
+    fn next_entry(&mut self) -> Option<(usize, TapeValue<'t, 'a>)> {
+        if self.next.index == self.end {
+            return None;
+        }
+        let key = self.next.index;
+        let value = self.next.at(self.next.next());
+        self.next = value.at(value.next());
+        Some((key, value))
+    }
+}
+
+impl<'t, 'a> Iterator for TapeObjectIter<'t, 'a> {
+    type Item = (&'t str, TapeValue<'t, 'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.next_entry()?;
+        Some((self.next.tape.str(key), value))
+    }
+}
+
+/// A [`Tape`] being parsed
+struct Builder<'a> {
+    // the length of the input, from which the offset of the remaining input is derived
+    len: usize,
+    strings: Vec<&'a str>,
+    words: Vec<u64>,
+    owned: String,
+}
+
+impl<'a> Builder<'a> {
+    /// Returns the offset in the input of `values`, the remaining input
+    #[inline]
+    fn offset(&self, values: &[u8]) -> usize {
+        self.len - values.len()
+    }
+
+    #[inline]
+    fn push(&mut self, tag: u8) {
+        self.words.push(word(tag, 0));
+    }
+
+    #[inline]
+    fn push_string(&mut self, tag: u8, string: Cow<'a, str>) {
+        match string {
+            Cow::Borrowed(string) => {
+                self.words.push(word(tag, self.strings.len() as u64));
+                self.strings.push(string);
+            }
+            // e.g. unescaped strings, which are not in the input
+            Cow::Owned(string) => {
+                self.words.push(word(tag, self.owned.len() as u64 | OWNED));
+                self.words.push(string.len() as u64);
+                self.owned.push_str(&string);
+            }
+        }
+    }
+
+    /// Pushes `number`, parsed from the input between the offsets `start` and `end`
+    #[inline]
+    fn push_number(&mut self, number: &Number<'a>, start: usize, end: usize) {
+        let (tag, mantissa, exponent) = match number {
+            Number::Integer(mantissa, exponent) => (INTEGER, mantissa, exponent),
+            Number::Float(mantissa, exponent) => (FLOAT, mantissa, exponent),
+        };
+        let len = match exponent.len() {
+            0 => mantissa.len(),
+            exponent => mantissa.len() + 1 + exponent,
+        };
+        // numbers borrowed from the input are its text, e.g. `1e5`, unless they were
+        // normalized, e.g. `+1` of the relaxed syntax
+        let borrowed = matches!((mantissa, exponent), (Cow::Borrowed(_), Cow::Borrowed(_)));
+        let offset = (borrowed && len == end - start).then_some(start as u64);
+        let payload = offset.unwrap_or_else(|| {
+            let payload = self.owned.len() as u64 | OWNED;
+            // numbers only contain ASCII
+            self.owned.push_str(core::str::from_utf8(mantissa).unwrap());
+            if !exponent.is_empty() {
+                self.owned.push('e');
+                self.owned.push_str(core::str::from_utf8(exponent).unwrap());
+            }
+            payload
+        });
+        self.words.push(word(tag, payload));
+        self.words.push(len as u64);
+    }
+
+    /// Pushes the start of an array or object, returning its position
+    #[inline]
+    fn start(&mut self) -> usize {
+        let start = self.words.len();
+        self.words.extend([0, 0]);
+        start
+    }
+
+    /// Pushes the end of the array or object that starts at `start` with `len` items
+    #[inline]
+    fn end(&mut self, start: usize, (start_tag, end_tag): (u8, u8), len: usize) {
+        let end = self.words.len();
+        self.words[start] = word(start_tag, end as u64);
+        self.words[start + 1] = len as u64;
+        self.words.push(word(end_tag, start as u64));
+    }
+}
+
+/// Parses a value nested in `depth` arrays or objects into `tape`
+fn parse_tape_value<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+    tape: &mut Builder<'a>,
+) -> Result<(), ErrorKind> {
+    skip_ignored(values, options)?;
+    let token = current_token(values)?;
+    match token {
+        b'{' => return parse_tape_object(values, options, depth, tape),
+        b'[' => return parse_tape_array(values, options, depth, tape),
+        b'"' => tape.push_string(STRING, parse_string(values)?),
+        b'\'' if options.relaxed => tape.push_string(STRING, parse_single_quoted(values)?),
+        b'n' => parse_null(values).map(|_| tape.push(NULL))?,
+        b't' => parse_true(values).map(|_| tape.push(TRUE))?,
+        b'f' => parse_false(values).map(|_| tape.push(FALSE))?,
+        token if is_number_start(token, options) => {
+            let start = tape.offset(values);
+            let number = parse_any_number(values, options)?;
+            tape.push_number(&number, start, tape.offset(values))
+        }
+        other => return Err(ErrorKind::InvalidToken(other)),
+    };
+    Ok(())
+}
+
+fn parse_tape_array<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+    tape: &mut Builder<'a>,
+) -> Result<(), ErrorKind> {
+    check_depth(options, depth)?;
+    *values = &values[1..];
+    let start = tape.start();
+    let mut len = 0;
+    loop {
+        skip_ignored(values, options)?;
+        let token = current_token(values)?;
+        if token == b']' {
+            *values = &values[1..];
+            break;
+        };
+        if len > 0 {
+            if token != b',' {
+                return Err(ErrorKind::MissingComma(token));
+            }
+            *values = &values[1..]; // consume ","
+            if is_trailing_comma(values, b']', options)? {
+                break;
+            }
+        }
+
+        parse_tape_value(values, options, depth + 1, tape)?;
+        len += 1;
+    }
+    tape.end(start, (START_ARRAY, END_ARRAY), len);
+    Ok(())
+}
+
+// assumes that `values` contains `{`
+fn parse_tape_object<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    depth: usize,
+    tape: &mut Builder<'a>,
+) -> Result<(), ErrorKind> {
+    check_depth(options, depth)?;
+    *values = &values[1..];
+    let start = tape.start();
+    // the keys seen so far, only tracked to reject repeated keys
+    let mut keys = BTreeSet::new();
+    let mut len = 0;
+    loop {
+        skip_ignored(values, options)?;
+        let token = current_token(values)?;
+        if token == b'}' {
+            *values = &values[1..];
+            break;
+        };
+        if len > 0 {
+            if token != b',' {
+                return Err(ErrorKind::MissingComma(token));
+            }
+            *values = &values[1..]; // consume ","
+            if is_trailing_comma(values, b'}', options)? {
+                break;
+            }
+            skip_ignored(values, options)?;
+        }
+
+        let key_start = *values;
+        let key = parse_key(values, options)?;
+        skip_ignored(values, options)?;
+        let token = current_token(values)?;
+        if token != b':' {
+            return Err(ErrorKind::InvalidToken(token));
+        };
+        *values = &values[1..];
+
+        tape.push_string(KEY, key.clone());
+        parse_tape_value(values, options, depth + 1, tape)?;
+        // like `parse`, the value is validated before the key is checked
+        if options.duplicate_keys == DuplicateKeys::Error && !keys.insert(key.clone()) {
+            *values = key_start;
+            return Err(ErrorKind::DuplicateKey(key.into_owned()));
+        }
+        len += 1;
+    }
+    tape.end(start, (START_OBJECT, END_OBJECT), len);
+    Ok(())
+}
+
+/// Parses JSON-compliant bytes into a [`Tape`]
+/// # Errors
+/// If and only if `json` is not valid JSON, with the same error [`parse`](crate::parse) returns.
+pub fn parse_tape(json: &[u8]) -> Result<Tape<'_>, Error> {
+    parse_tape_with_options(json, &ParseOptions::default())
+}
+
+/// Parses JSON-compliant bytes into a [`Tape`] according to `options`
+/// # Errors
+/// If `json` is not valid JSON or it does not fulfill `options`, with the same error
+/// [`parse_with_options`](crate::parse_with_options) returns.
+pub fn parse_tape_with_options<'a>(
+    json: &'a [u8],
+    options: &ParseOptions,
+) -> Result<Tape<'a>, Error> {
+    let mut tape = Builder {
+        len: json.len(),
+        strings: vec![],
+        words: vec![],
+        owned: String::new(),
+    };
+    let mut values = json;
+    parse_tape_document(&mut values, options, &mut tape).map_err(|e| e.locate(json, values))?;
+    Ok(Tape {
+        json,
+        strings: tape.strings,
+        words: tape.words,
+        owned: tape.owned,
+        duplicate_keys: options.duplicate_keys,
+    })
+}
+
+fn parse_tape_document<'a>(
+    values: &mut &'a [u8],
+    options: &ParseOptions,
+    tape: &mut Builder<'a>,
+) -> Result<(), ErrorKind> {
+    parse_tape_value(values, options, 0, tape)?;
+    skip_ignored(values, options)?;
+    if values.is_empty() {
+        Ok(())
+    } else {
+        Err(ErrorKind::InvalidEOF)
+    }
+}
@@ -0,0 +1,94 @@
+use alloc::borrow::Cow;
+
+use super::error::*;
+use super::options::ParseOptions;
+use super::parser::Number;
+use super::reader::{Event, Reader};
+
+/// Callbacks called by [`visit`] for each token of a JSON document, in order.
+///
+/// All methods do nothing by default. Errors returned by them stop the parsing and are
+/// returned by [`visit`], located at the end of the token that was visited.
+pub trait Visitor<'a> {
+    /// Called for `null`
+    fn visit_null(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+
+    /// Called for `true` and `false`
+    fn visit_bool(&mut self, value: bool) -> Result<(), ErrorKind> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Called for a number
+    fn visit_number(&mut self, value: Number<'a>) -> Result<(), ErrorKind> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Called for a string that is not a key
+    fn visit_str(&mut self, value: Cow<'a, str>) -> Result<(), ErrorKind> {
+        let _ = value;
+        Ok(())
+    }
+
+    /// Called for `{`
+    fn begin_object(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+
+    /// Called for the key of each item of an object, before its value is visited
+    fn key(&mut self, key: Cow<'a, str>) -> Result<(), ErrorKind> {
+        let _ = key;
+        Ok(())
+    }
+
+    /// Called for `}`
+    fn end_object(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+
+    /// Called for `[`
+    fn begin_array(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+
+    /// Called for `]`
+    fn end_array(&mut self) -> Result<(), ErrorKind> {
+        Ok(())
+    }
+}
+
+/// Parses `json`, calling `visitor` for each token instead of building a [`Value`](crate::Value).
+/// # Errors
+/// If `json` is not valid JSON or `visitor` errors.
+pub fn visit<'a, V: Visitor<'a>>(json: &'a [u8], visitor: &mut V) -> Result<(), Error> {
+    visit_with_options(json, &ParseOptions::default(), visitor)
+}
+
+/// Parses `json` according to `options`, calling `visitor` for each token.
+/// # Errors
+/// If `json` is not valid JSON, it does not fulfill `options` or `visitor` errors.
+pub fn visit_with_options<'a, V: Visitor<'a>>(
+    json: &'a [u8],
+    options: &ParseOptions,
+    visitor: &mut V,
+) -> Result<(), Error> {
+    let mut reader = Reader::with_options(json, options.clone());
+    while let Some(event) = reader.next() {
+        match event? {
+            Event::StartObject => visitor.begin_object(),
+            Event::Key(key) => visitor.key(key),
+            Event::EndObject => visitor.end_object(),
+            Event::StartArray => visitor.begin_array(),
+            Event::EndArray => visitor.end_array(),
+            Event::String(value) => visitor.visit_str(value),
+            Event::Number(value) => visitor.visit_number(value),
+            Event::Bool(value) => visitor.visit_bool(value),
+            Event::Null => visitor.visit_null(),
+        }
+        .map_err(|e| Error::new(e, reader.byte_offset()))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Write};
+
+use super::parser::{Number, Value};
+
+const INDENT: &str = "  ";
+
+/// Writes `value` as compact JSON into `writer`.
+///
+/// Numbers are written exactly as they were parsed, except `Infinity` and `NaN` of the relaxed
+/// syntax, which are not JSON and are written as `null`.
+/// # Errors
+/// If `writer` errors or a [`Number`] is not valid UTF-8.
+pub fn write<W: Write>(writer: &mut W, value: &Value) -> fmt::Result {
+    write_value(writer, value, None)
+}
+
+/// Writes `value` as pretty-printed JSON into `writer`, indenting nested values by 2 spaces.
+/// # Errors
+/// If `writer` errors or a [`Number`] is not valid UTF-8.
+pub fn write_pretty<W: Write>(writer: &mut W, value: &Value) -> fmt::Result {
+    write_value(writer, value, Some(0))
+}
+
+/// Returns `value` as compact JSON
+/// # Panics
+/// If a [`Number`] is not valid UTF-8, which never happens for numbers returned by [`parse`](crate::parse).
+pub fn to_string(value: &Value) -> String {
+    let mut string = String::new();
+    write(&mut string, value).expect("numbers to be valid UTF-8");
+    string
+}
+
+/// Returns `value` as pretty-printed JSON
+/// # Panics
+/// If a [`Number`] is not valid UTF-8, which never happens for numbers returned by [`parse`](crate::parse).
+pub fn to_string_pretty(value: &Value) -> String {
+    let mut string = String::new();
+    write_pretty(&mut string, value).expect("numbers to be valid UTF-8");
+    string
+}
+
+/// Returns `value` as compact JSON bytes
+/// # Panics
+/// If a [`Number`] is not valid UTF-8, which never happens for numbers returned by [`parse`](crate::parse).
+pub fn to_vec(value: &Value) -> Vec<u8> {
+    to_string(value).into_bytes()
+}
+
+/// Writes compact JSON, or pretty-printed JSON with the alternate flag (`{:#}`)
+impl Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if f.alternate() {
+            write_pretty(f, self)
+        } else {
+            write(f, self)
+        }
+    }
+}
+
+/// Writes `value`, pretty-printed at `indent` levels when it is `Some`.
+fn write_value<W: Write>(writer: &mut W, value: &Value, indent: Option<usize>) -> fmt::Result {
+    match value {
+        Value::Null => writer.write_str("null"),
+        Value::Bool(true) => writer.write_str("true"),
+        Value::Bool(false) => writer.write_str("false"),
+        Value::Number(number) => write_number(writer, number),
+        Value::String(string) => write_string(writer, string),
+        Value::Array(array) => {
+            if array.is_empty() {
+                return writer.write_str("[]");
+            }
+            writer.write_char('[')?;
+            for (i, item) in array.iter().enumerate() {
+                if i > 0 {
+                    writer.write_char(',')?;
+                }
+                write_newline(writer, indent.map(|x| x + 1))?;
+                write_value(writer, item, indent.map(|x| x + 1))?;
+            }
+            write_newline(writer, indent)?;
+            writer.write_char(']')
+        }
+        Value::Object(object) => write_object(
+            writer,
+            object.iter().map(|(key, item)| (key.as_ref(), item)),
+            indent,
+        ),
+        Value::MultiObject(entries) => write_object(
+            writer,
+            entries.iter().map(|(key, item)| (key.as_ref(), item)),
+            indent,
+        ),
+    }
+}
+
+fn write_object<'b, 'c: 'b, W: Write>(
+    writer: &mut W,
+    entries: impl Iterator<Item = (&'b str, &'b Value<'c>)>,
+    indent: Option<usize>,
+) -> fmt::Result {
+    let mut entries = entries.peekable();
+    if entries.peek().is_none() {
+        return writer.write_str("{}");
+    }
+    writer.write_char('{')?;
+    for (i, (key, item)) in entries.enumerate() {
+        if i > 0 {
+            writer.write_char(',')?;
+        }
+        write_newline(writer, indent.map(|x| x + 1))?;
+        write_string(writer, key)?;
+        writer.write_str(if indent.is_some() { ": " } else { ":" })?;
+        write_value(writer, item, indent.map(|x| x + 1))?;
+    }
+    write_newline(writer, indent)?;
+    writer.write_char('}')
+}
+
+#[inline]
+fn write_newline<W: Write>(writer: &mut W, indent: Option<usize>) -> fmt::Result {
+    if let Some(indent) = indent {
+        writer.write_char('\n')?;
+        for _ in 0..indent {
+            writer.write_str(INDENT)?;
+        }
+    }
+    Ok(())
+}
+
+#[inline]
+fn write_number<W: Write>(writer: &mut W, number: &Number) -> fmt::Result {
+    if number.non_finite().is_some() {
+        return writer.write_str("null");
+    }
+    let (Number::Float(mantissa, exponent) | Number::Integer(mantissa, exponent)) = number;
+    writer.write_str(core::str::from_utf8(mantissa).map_err(|_| fmt::Error)?)?;
+    if !exponent.is_empty() {
+        writer.write_char('e')?;
+        writer.write_str(core::str::from_utf8(exponent).map_err(|_| fmt::Error)?)?;
+    }
+    Ok(())
+}
+
+fn write_string<W: Write>(writer: &mut W, string: &str) -> fmt::Result {
+    writer.write_char('"')?;
+    let mut start = 0;
+    for (i, byte) in string.bytes().enumerate() {
+        let escape = match byte {
+            b'"' => Some("\\\""),
+            b'\\' => Some("\\\\"),
+            b'\n' => Some("\\n"),
+            b'\r' => Some("\\r"),
+            b'\t' => Some("\\t"),
+            0x08 => Some("\\b"),
+            0x0c => Some("\\f"),
+            0..=0x1f => None,
+            _ => continue,
+        };
+        // `i` is always at a char boundary since the escaped bytes are ASCII
+        writer.write_str(&string[start..i])?;
+        match escape {
+            Some(escape) => writer.write_str(escape)?,
+            None => write!(writer, "\\u{:04x}", byte)?,
+        }
+        start = i + 1;
+    }
+    writer.write_str(&string[start..])?;
+    writer.write_char('"')
+}
@@ -0,0 +1,296 @@
+use std::sync::Arc;
+
+use arrow_array::builder::{Int64Builder, ListBuilder, StringBuilder};
+use arrow_array::{
+    Array, ArrayRef, BooleanArray, Float64Array, Int32Array, Int64Array, LargeStringArray,
+    NullArray, RecordBatch, StringArray, StructArray, UInt8Array,
+};
+use arrow_schema::{DataType, Field, Fields, Schema, SchemaRef};
+
+use json_deserializer::{
+    infer_ndjson, parse_ndjson_record_batches, parse_ndjson_record_batches_with_options,
+    parse_record_batches, parse_record_batches_with_options, ArrowOptions, DuplicateKeys, Error,
+    ErrorKind, InferredType, ParseOptions,
+};
+
+fn schema(fields: Vec<Field>) -> SchemaRef {
+    Arc::new(Schema::new(fields))
+}
+
+fn custom(message: &str, offset: usize) -> Error {
+    Error::new(ErrorKind::Custom(message.to_string()), offset)
+}
+
+#[test]
+fn primitives() -> Result<(), Error> {
+    let schema = schema(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Float64, true),
+        Field::new("c", DataType::Boolean, true),
+        Field::new("d", DataType::Utf8, true),
+        Field::new("e", DataType::LargeUtf8, false),
+        Field::new("f", DataType::Null, true),
+        Field::new("g", DataType::UInt8, true),
+    ]);
+    let data: &[u8] = br#"[
+        {"a": 1, "b": 1.5, "c": true, "d": "x", "e": "y\n", "f": null, "g": 255},
+        {"e": "", "unknown": {"a": [1, {"b": 2}]}, "a": null, "d": null},
+        {"b": 2, "e": "z", "c": false, "a": -3}
+    ]"#;
+
+    let batches = parse_record_batches(data, schema.clone())?;
+    assert_eq!(batches.len(), 1);
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(vec![Some(1), None, Some(-3)])),
+        Arc::new(Float64Array::from(vec![Some(1.5), None, Some(2.0)])),
+        Arc::new(BooleanArray::from(vec![Some(true), None, Some(false)])),
+        Arc::new(StringArray::from(vec![Some("x"), None, None])),
+        Arc::new(LargeStringArray::from(vec!["y\n", "", "z"])),
+        Arc::new(NullArray::new(3)),
+        Arc::new(UInt8Array::from(vec![Some(255), None, None])),
+    ];
+    assert_eq!(batches[0], RecordBatch::try_new(schema, columns).unwrap());
+    Ok(())
+}
+
+#[test]
+fn nested() -> Result<(), Error> {
+    let point = Fields::from(vec![
+        Field::new("x", DataType::Int32, false),
+        Field::new("tags", DataType::new_list(DataType::Utf8, true), true),
+    ]);
+    let schema = schema(vec![
+        Field::new("p", DataType::Struct(point.clone()), true),
+        Field::new(
+            "m",
+            DataType::new_list(DataType::new_list(DataType::Int64, true), true),
+            true,
+        ),
+    ]);
+    let data: &[u8] = br#"[
+        {"p": {"x": 1, "tags": ["a", null]}, "m": [[1, 2], [], null]},
+        {"p": null, "m": null},
+        {"p": {"tags": [], "x": 2}},
+        {"p": {"x": 3, "y": 4}, "m": []}
+    ]"#;
+
+    let batches = parse_record_batches(data, schema.clone())?;
+
+    let mut tags = ListBuilder::new(StringBuilder::new());
+    tags.append_value([Some("a"), None]);
+    tags.append_null();
+    tags.append_value([] as [Option<&str>; 0]);
+    tags.append_null();
+    let p = StructArray::try_new(
+        point,
+        vec![
+            Arc::new(Int32Array::from(vec![1, 0, 2, 3])),
+            Arc::new(tags.finish()),
+        ],
+        Some(vec![true, false, true, true].into()),
+    )
+    .unwrap();
+
+    let mut m = ListBuilder::new(ListBuilder::new(Int64Builder::new()));
+    m.append_value([Some(vec![Some(1), Some(2)]), Some(vec![]), None]);
+    m.append_null();
+    m.append_null();
+    m.append_value([] as [Option<Vec<Option<i64>>>; 0]);
+
+    let expected = RecordBatch::try_new(schema, vec![Arc::new(p), Arc::new(m.finish())]).unwrap();
+    assert_eq!(batches, vec![expected]);
+    Ok(())
+}
+
+#[test]
+fn batch_size() -> Result<(), Error> {
+    let schema = schema(vec![Field::new("a", DataType::Int64, true)]);
+    let data: &[u8] = br#"[{"a": 1}, {"a": 2}, {}, {"a": 4}, {"a": 5}]"#;
+    let options = ArrowOptions::default().with_batch_size(2);
+
+    let batches = parse_record_batches_with_options(data, schema.clone(), &options)?;
+    let lengths = batches.iter().map(|x| x.num_rows()).collect::<Vec<_>>();
+    assert_eq!(lengths, vec![2, 2, 1]);
+    assert_eq!(
+        batches[1].column(0).as_ref(),
+        &Int64Array::from(vec![None, Some(4)]) as &dyn Array
+    );
+
+    assert_eq!(parse_record_batches(b"[]", schema.clone())?, vec![]);
+
+    // records without fields are still rows
+    let batches = parse_record_batches(b"[{}, {}]", Arc::new(Schema::empty()))?;
+    assert_eq!(batches[0].num_rows(), 2);
+    Ok(())
+}
+
+#[test]
+fn ndjson() -> Result<(), Error> {
+    let schema = schema(vec![
+        Field::new("a", DataType::Int64, true),
+        Field::new("b", DataType::Utf8, true),
+    ]);
+    let data: &[u8] = b"{\"a\": 1, \"b\": \"x\"}\n\n  \n{\"b\": \"y\"}\r\n{\"a\": 3}\n";
+
+    let batches = parse_ndjson_record_batches(data, schema.clone())?;
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(Int64Array::from(vec![Some(1), None, Some(3)])),
+        Arc::new(StringArray::from(vec![Some("x"), Some("y"), None])),
+    ];
+    assert_eq!(
+        batches,
+        vec![RecordBatch::try_new(schema.clone(), columns).unwrap()]
+    );
+
+    let options = ArrowOptions::default().with_batch_size(1);
+    let batches = parse_ndjson_record_batches_with_options(data, schema.clone(), &options)?;
+    assert_eq!(batches.len(), 3);
+
+    // offsets are in the whole input
+    assert_eq!(
+        parse_ndjson_record_batches(b"{\"a\": 1}\n{\"a\": 1 2}", schema.clone()),
+        Err(Error::new(ErrorKind::MissingComma(b'2'), 17))
+    );
+    assert_eq!(
+        parse_ndjson_record_batches(b"{\"a\": 1}\n[1]", schema),
+        Err(custom("expected a record, found an array", 10))
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let schema = schema(vec![
+        Field::new("a", DataType::Int8, false),
+        Field::new("b", DataType::new_list(DataType::Boolean, false), true),
+    ]);
+    let cases: &[(&[u8], Error)] = &[
+        (
+            br#"[{"a": "1"}]"#,
+            custom("field \"a\" of type Int8 can't be a string", 10),
+        ),
+        (
+            br#"[{"a": null}]"#,
+            custom("field \"a\" of type Int8 can't be null", 11),
+        ),
+        (br#"[{"b": []}]"#, custom("missing field \"a\"", 10)),
+        (
+            br#"[{"a": 1, "b": [true, null]}]"#,
+            custom("field \"item\" of type Boolean can't be null", 26),
+        ),
+        (
+            br#"[{"a": 1, "b": {}}]"#,
+            custom(
+                "field \"b\" of type List(non-null Boolean) can't be an object",
+                16,
+            ),
+        ),
+        (
+            br#"[{"a": 128}]"#,
+            Error::new(ErrorKind::NumberOverflow, 10),
+        ),
+        (
+            br#"[{"a": 1, "a": 2}]"#,
+            Error::new(ErrorKind::DuplicateKey("a".to_string()), 14),
+        ),
+        (
+            br#"{"a": 1}"#,
+            custom("expected an array of records, found an object", 1),
+        ),
+        (br#"[1]"#, custom("expected a record, found a number", 2)),
+        (
+            br#"[{"a": 1} 2]"#,
+            Error::new(ErrorKind::MissingComma(b'2'), 10),
+        ),
+    ];
+    for (data, expected) in cases {
+        assert_eq!(
+            parse_record_batches(data, schema.clone()),
+            Err(expected.clone()),
+            "{}",
+            String::from_utf8_lossy(data)
+        );
+    }
+
+    let unsupported = self::schema(vec![Field::new("a", DataType::Date32, true)]);
+    assert_eq!(
+        parse_record_batches(b"[]", unsupported),
+        Err(custom("field \"a\" has the unsupported type Date32", 0))
+    );
+}
+
+#[test]
+fn duplicate_keys() -> Result<(), Error> {
+    let schema = schema(vec![
+        Field::new("a", DataType::Int64, false),
+        Field::new("b", DataType::Boolean, true),
+    ]);
+    let data: &[u8] = br#"[{"a": 1, "b": true, "a": [2]}, {"a": 3}]"#;
+
+    let options = ArrowOptions::default()
+        .with_parse(ParseOptions::default().with_duplicate_keys(DuplicateKeys::FirstWins));
+    let batches = parse_record_batches_with_options(data, schema.clone(), &options)?;
+    let a: ArrayRef = Arc::new(Int64Array::from(vec![1, 3]));
+    let b: ArrayRef = Arc::new(BooleanArray::from(vec![Some(true), None]));
+    assert_eq!(batches[0].columns(), &[a, b]);
+
+    // appended values can't be replaced
+    for policy in [
+        DuplicateKeys::LastWins,
+        DuplicateKeys::KeepAll,
+        DuplicateKeys::Error,
+    ] {
+        let options =
+            ArrowOptions::default().with_parse(ParseOptions::default().with_duplicate_keys(policy));
+        assert_eq!(
+            parse_record_batches_with_options(data, schema.clone(), &options),
+            Err(Error::new(ErrorKind::DuplicateKey("a".to_string()), 25)),
+            "{:?}",
+            policy
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn parse_options() {
+    let schema = schema(vec![Field::new(
+        "a",
+        DataType::new_list(DataType::Int64, true),
+        true,
+    )]);
+    let options = ArrowOptions::default().with_parse(ParseOptions::default().with_max_depth(2));
+    assert!(
+        parse_record_batches_with_options(br#"[{"a": null}]"#, schema.clone(), &options).is_ok()
+    );
+    assert_eq!(
+        parse_record_batches_with_options(br#"[{"a": [1]}]"#, schema, &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 7))
+    );
+}
+
+#[test]
+fn inferred_schema() -> Result<(), Error> {
+    let data: &[u8] = b"{\"a\": 1, \"b\": [{\"c\": \"x\"}]}\n{\"a\": 2.5, \"d\": null}";
+    let schema = infer_ndjson(data)?.to_arrow_schema().unwrap();
+    let c = Field::new("c", DataType::Utf8, false);
+    let expected = Schema::new(vec![
+        Field::new("a", DataType::Float64, false),
+        Field::new(
+            "b",
+            DataType::new_list(DataType::Struct(vec![c].into()), false),
+            true,
+        ),
+        Field::new("d", DataType::Null, true),
+    ]);
+    assert_eq!(schema, expected);
+
+    let batches = parse_ndjson_record_batches(data, Arc::new(schema))?;
+    assert_eq!(batches[0].num_rows(), 2);
+
+    assert!(InferredType::Integer.to_arrow_schema().is_err());
+    assert!(infer_ndjson(b"{\"a\": 1}\n{\"a\": \"b\"}")?
+        .to_arrow_schema()
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_lazy_with_options, parse_pointer_with_options, parse_projected_with_options,
+    parse_stream_with_options, parse_with_options, to_string, DuplicateKeys, Error, ErrorKind,
+    ParseOptions, Projection, Value,
+};
+
+fn options(duplicate_keys: DuplicateKeys) -> ParseOptions {
+    ParseOptions::default().with_duplicate_keys(duplicate_keys)
+}
+
+const DATA: &[u8] = br#"{"a": 1, "b": {"c": 2, "c": 3}, "a": 4}"#;
+
+#[test]
+fn last_wins() -> Result<(), Error> {
+    let expected = parse(br#"{"a": 4, "b": {"c": 3}}"#)?;
+    assert_eq!(parse(DATA)?, expected);
+    assert_eq!(
+        parse_with_options(DATA, &options(DuplicateKeys::LastWins))?,
+        expected
+    );
+    Ok(())
+}
+
+#[test]
+fn first_wins() -> Result<(), Error> {
+    let value = parse_with_options(DATA, &options(DuplicateKeys::FirstWins))?;
+    assert_eq!(value, parse(br#"{"a": 1, "b": {"c": 2}}"#)?);
+    Ok(())
+}
+
+#[test]
+fn error() {
+    let options = options(DuplicateKeys::Error);
+    assert_eq!(
+        parse_with_options(DATA, &options),
+        Err(Error::new(ErrorKind::DuplicateKey("c".to_string()), 23))
+    );
+
+    // keys are compared after unescaping
+    assert_eq!(
+        parse_with_options(br#"{"a": 1, "\u0061": 2}"#, &options),
+        Err(Error::new(ErrorKind::DuplicateKey("a".to_string()), 9))
+    );
+
+    // the value is validated before the key is checked
+    assert_eq!(
+        parse_with_options(br#"{"a": 1, "a": [1 2]}"#, &options),
+        Err(Error::new(ErrorKind::MissingComma(b'2'), 17))
+    );
+
+    // keys are only compared within the same object
+    assert!(parse_with_options(br#"{"a": {"a": 1}, "b": [{"a": 1}, {"a": 2}]}"#, &options).is_ok());
+}
+
+#[test]
+fn keep_all() -> Result<(), Error> {
+    let value = parse_with_options(DATA, &options(DuplicateKeys::KeepAll))?;
+    let number = |x: &'static [u8]| parse(x).unwrap();
+    let expected = Value::MultiObject(vec![
+        ("a".into(), number(b"1")),
+        (
+            "b".into(),
+            Value::MultiObject(vec![("c".into(), number(b"2")), ("c".into(), number(b"3"))]),
+        ),
+        ("a".into(), number(b"4")),
+    ]);
+    assert_eq!(value, expected);
+
+    assert_eq!(to_string(&value), r#"{"a":1,"b":{"c":2,"c":3},"a":4}"#);
+    assert_eq!(value.pointer("/a"), Some(&number(b"4")));
+    assert_eq!(value.pointer("/b/c"), Some(&number(b"3")));
+    Ok(())
+}
+
+#[test]
+fn entry_points() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": 1, "a": 2} {"b": 3}"#;
+
+    let options = options(DuplicateKeys::FirstWins);
+    let values = parse_stream_with_options(data, options.clone())
+        .map(|item| item.map(|(value, _)| value))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, vec![parse(br#"{"a": 1}"#)?, parse(br#"{"b": 3}"#)?]);
+
+    let lazy = parse_lazy_with_options(br#"{"a": 1, "a": 2}"#, &options)?;
+    assert_eq!(lazy.get("a")?.unwrap().as_bytes(), b"1");
+    assert_eq!(lazy.to_value()?, parse(br#"{"a": 1}"#)?);
+    Ok(())
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn preserve_order() -> Result<(), Error> {
+    let data: &[u8] = br#"{"b": 1, "a": 2, "b": 3}"#;
+
+    let value = parse_with_options(data, &options(DuplicateKeys::LastWins))?;
+    assert_eq!(to_string(&value), r#"{"b":3,"a":2}"#);
+
+    let value = parse_with_options(data, &options(DuplicateKeys::FirstWins))?;
+    assert_eq!(to_string(&value), r#"{"b":1,"a":2}"#);
+    Ok(())
+}
+
+/// Removes the fields of objects in `value` whose keys are not `key`
+fn project<'a>(value: Value<'a>, key: &str) -> Value<'a> {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .filter(|(k, _)| k == key)
+                .map(|(k, v)| (k, project(v, key)))
+                .collect(),
+        ),
+        Value::MultiObject(entries) => Value::MultiObject(
+            entries
+                .into_iter()
+                .filter(|(k, _)| k == key)
+                .map(|(k, v)| (k, project(v, key)))
+                .collect(),
+        ),
+        Value::Array(array) => Value::Array(array.into_iter().map(|x| project(x, key)).collect()),
+        other => other,
+    }
+}
+
+fn policy() -> impl Strategy<Value = DuplicateKeys> {
+    prop_oneof![
+        Just(DuplicateKeys::LastWins),
+        Just(DuplicateKeys::FirstWins),
+        Just(DuplicateKeys::Error),
+        Just(DuplicateKeys::KeepAll),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn projection_same_as_parse(data in "[\\[\\]{}\":,0-1ab ]{0,20}", policy in policy()) {
+        let options = options(policy);
+        let projection = Projection::new([["a"; 4]]);
+        let expected = parse_with_options(data.as_bytes(), &options).map(|value| project(value, "a"));
+        prop_assert_eq!(
+            parse_projected_with_options(data.as_bytes(), &projection, &options),
+            expected
+        );
+    }
+
+    #[test]
+    fn pointer_same_as_parse(
+        data in "[\\[\\]{}\":,0-1ab ]{0,20}",
+        pointer in "(/[0-1ab]{0,1}){0,3}",
+        // objects along the path are not checked for repeated keys
+        policy in policy().prop_filter("no errors", |x| *x != DuplicateKeys::Error)
+    ) {
+        let options = options(policy);
+        let expected = parse_with_options(data.as_bytes(), &options)
+            .map(|value| value.pointer(&pointer).cloned());
+        prop_assert_eq!(parse_pointer_with_options(data.as_bytes(), &pointer, &options), expected);
+    }
+}
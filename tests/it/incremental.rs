@@ -0,0 +1,159 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    Error, ErrorKind, Event, IncrementalReader, Number, ParseOptions, Reader, Status,
+};
+
+/// Reads `json` fed in `chunks`, returning the debug representation of the events
+fn read_chunks<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Result<Vec<String>, Error> {
+    let mut reader = IncrementalReader::new();
+    let mut events = vec![];
+    for chunk in chunks {
+        reader.feed(chunk);
+        while let Status::Event(event) = reader.next_event()? {
+            events.push(format!("{:?}", event));
+        }
+    }
+    reader.finish();
+    while let Status::Event(event) = reader.next_event()? {
+        events.push(format!("{:?}", event));
+    }
+    Ok(events)
+}
+
+fn read(json: &[u8]) -> Result<Vec<String>, Error> {
+    Reader::new(json)
+        .map(|event| event.map(|event| format!("{:?}", event)))
+        .collect()
+}
+
+#[test]
+fn need_more_input() -> Result<(), Error> {
+    let mut reader = IncrementalReader::new();
+    assert_eq!(reader.next_event()?, Status::NeedMoreInput);
+    reader.feed(b"[tr");
+    assert_eq!(reader.next_event()?, Status::Event(Event::StartArray));
+    assert_eq!(reader.next_event()?, Status::NeedMoreInput);
+    reader.feed(b"ue, 12");
+    assert_eq!(reader.next_event()?, Status::Event(Event::Bool(true)));
+    // the number may continue in the next chunk
+    assert_eq!(reader.next_event()?, Status::NeedMoreInput);
+    reader.feed(b"3, \"a\\u00");
+    assert_eq!(
+        reader.next_event()?,
+        Status::Event(Event::Number(Number::Integer(b"123".into(), b"".into())))
+    );
+    assert_eq!(reader.next_event()?, Status::NeedMoreInput);
+    reader.feed(b"e9\"]");
+    assert_eq!(
+        reader.next_event()?,
+        Status::Event(Event::String("aé".into()))
+    );
+    assert_eq!(reader.next_event()?, Status::Event(Event::EndArray));
+    assert_eq!(reader.next_event()?, Status::NeedMoreInput);
+    assert_eq!(reader.byte_offset(), 22);
+    reader.finish();
+    assert_eq!(reader.next_event()?, Status::Finished);
+    assert_eq!(reader.next_event()?, Status::Finished);
+    Ok(())
+}
+
+#[test]
+fn number_at_the_end() -> Result<(), Error> {
+    let mut reader = IncrementalReader::new();
+    reader.feed(b"1");
+    reader.feed(b"0");
+    assert_eq!(reader.next_event()?, Status::NeedMoreInput);
+    reader.finish();
+    assert_eq!(
+        reader.next_event()?,
+        Status::Event(Event::Number(Number::Integer(b"10".into(), b"".into())))
+    );
+    assert_eq!(reader.next_event()?, Status::Finished);
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let mut reader = IncrementalReader::new();
+    reader.feed(b"[1, ");
+    reader.feed(b"2 3]");
+    assert_eq!(reader.next_event(), Ok(Status::Event(Event::StartArray)));
+    assert!(matches!(reader.next_event(), Ok(Status::Event(_))));
+    assert!(matches!(reader.next_event(), Ok(Status::Event(_))));
+    assert_eq!(
+        reader.next_event(),
+        Err(Error::new(ErrorKind::MissingComma(b'3'), 6))
+    );
+    assert_eq!(reader.next_event(), Ok(Status::Finished));
+
+    let mut reader = IncrementalReader::new();
+    reader.feed(b"[\"a");
+    reader.finish();
+    assert_eq!(reader.next_event(), Ok(Status::Event(Event::StartArray)));
+    assert_eq!(
+        reader.next_event(),
+        Err(Error::new(ErrorKind::InvalidEOF, 3))
+    );
+}
+
+#[test]
+fn max_depth() {
+    let mut reader = IncrementalReader::with_options(ParseOptions::default().with_max_depth(1));
+    reader.feed(b"[[");
+    assert_eq!(reader.next_event(), Ok(Status::Event(Event::StartArray)));
+    assert_eq!(
+        reader.next_event(),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 1))
+    );
+}
+
+#[test]
+fn relaxed_is_unsupported() {
+    let mut reader = IncrementalReader::with_options(ParseOptions::default().with_relaxed(true));
+    reader.feed(b"[1]");
+    assert_eq!(
+        reader.next_event(),
+        Err(Error::new(ErrorKind::UnsupportedOption("relaxed"), 0))
+    );
+    assert_eq!(reader.next_event(), Ok(Status::Finished));
+}
+
+#[test]
+fn byte_by_byte() {
+    for name in ["pass1", "pass2", "pass3"] {
+        let data =
+            std::fs::read(format!("tests/it/json_org_validator_tests/{}.json", name)).unwrap();
+        assert_eq!(read_chunks(data.chunks(1)), read(&data), "{}", name);
+    }
+    for i in 1..=33 {
+        let data =
+            std::fs::read(format!("tests/it/json_org_validator_tests/fail{}.json", i)).unwrap();
+        assert_eq!(read_chunks(data.chunks(1)), read(&data), "fail{}.json", i);
+    }
+}
+
+proptest! {
+    #[test]
+    fn same_as_reader(
+        data in "[\\[\\]{}\":,0-9a-z\\\\ .eE+-]{0,30}",
+        splits in prop::collection::vec(0..30usize, 0..5)
+    ) {
+        let data = data.as_bytes();
+        let mut splits = splits.into_iter().map(|x| x.min(data.len())).collect::<Vec<_>>();
+        splits.push(0);
+        splits.push(data.len());
+        splits.sort_unstable();
+        let chunks = splits.windows(2).map(|x| &data[x[0]..x[1]]);
+
+        prop_assert_eq!(read_chunks(chunks), read(data));
+    }
+
+    #[test]
+    fn split_strings(value in any::<Vec<String>>(), split in any::<prop::sample::Index>()) {
+        let data = serde_json::to_vec(&value).unwrap();
+        let (a, b) = data.split_at(split.index(data.len() + 1));
+
+        prop_assert_eq!(read_chunks([a, b]), read(&data));
+    }
+}
@@ -0,0 +1,193 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    infer, infer_ndjson, infer_ndjson_with_options, parse, parse_ndjson_with_options, to_string,
+    Error, ErrorKind, Inference, InferredField, InferredType, NdjsonOptions, ParseOptions,
+};
+
+fn field(name: &str, data_type: InferredType, nullable: bool) -> InferredField {
+    InferredField {
+        name: name.to_string(),
+        data_type,
+        nullable,
+    }
+}
+
+fn list(data_type: InferredType, nullable: bool) -> InferredType {
+    InferredType::List(Box::new(field("item", data_type, nullable)))
+}
+
+/// Sorts the fields of structs by name, as they are when inferred from objects
+/// without `preserve_order`
+fn sorted(data_type: InferredType) -> InferredType {
+    let sort_field = |mut field: InferredField| {
+        field.data_type = sorted(field.data_type);
+        field
+    };
+    match data_type {
+        InferredType::List(item) => InferredType::List(Box::new(sort_field(*item))),
+        InferredType::Struct(fields) => {
+            let mut fields = fields.into_iter().map(sort_field).collect::<Vec<_>>();
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+            InferredType::Struct(fields)
+        }
+        InferredType::Union(types) => InferredType::Union(types.into_iter().map(sorted).collect()),
+        other => other,
+    }
+}
+
+fn infer_json(examples: &[&[u8]]) -> Result<InferredType, Error> {
+    let mut inference = Inference::new();
+    for json in examples {
+        inference.add_json(json)?;
+    }
+    Ok(inference.finish())
+}
+
+#[test]
+fn scalars() -> Result<(), Error> {
+    let cases: &[(&[&[u8]], InferredType)] = &[
+        (&[], InferredType::Null),
+        (&[b"null"], InferredType::Null),
+        (&[b"true", b"null"], InferredType::Boolean),
+        (&[b"1", b"-2", b"1E2"], InferredType::Integer),
+        (&[b"1", b"1.5"], InferredType::Float),
+        (&[b"1e-2", b"1"], InferredType::Float),
+        (&[br#""a""#], InferredType::String),
+        (
+            &[b"1", br#""a""#, b"2.5", b"false", b"null", br#""b""#],
+            InferredType::Union(vec![
+                InferredType::Float,
+                InferredType::String,
+                InferredType::Boolean,
+            ]),
+        ),
+    ];
+    for (examples, expected) in cases {
+        assert_eq!(infer_json(examples)?, *expected, "{:?}", examples);
+        let values = examples
+            .iter()
+            .map(|x| parse(x))
+            .collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(infer(&values), *expected, "{:?}", examples);
+    }
+    Ok(())
+}
+
+#[test]
+fn records() -> Result<(), Error> {
+    let data: &[u8] = br#"{"id": 1, "name": "a", "tags": ["x"], "point": {"x": 1, "y": 2}}
+{"id": 2, "name": null, "tags": [], "point": {"x": 1.5}}
+
+{"id": 3, "tags": ["y", null], "point": {"x": 2, "y": 3}, "extra": [[1], [2.5], null]}
+"#;
+    let expected = InferredType::Struct(vec![
+        field("id", InferredType::Integer, false),
+        field("name", InferredType::String, true),
+        field("tags", list(InferredType::String, true), false),
+        field(
+            "point",
+            InferredType::Struct(vec![
+                field("x", InferredType::Float, false),
+                field("y", InferredType::Integer, true),
+            ]),
+            false,
+        ),
+        field("extra", list(list(InferredType::Float, false), true), true),
+    ]);
+    assert_eq!(infer_ndjson(data)?, expected);
+
+    let options = NdjsonOptions::default().with_skip_blank_lines(true);
+    let values = parse_ndjson_with_options(data, options).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(sorted(infer(&values)), sorted(expected));
+    Ok(())
+}
+
+#[test]
+fn unions() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": [1, "x"]}
+{"a": {"b": 1}}
+{"a": ["y", 2.5, true]}
+{"a": {"c": "z"}}"#;
+    let expected = InferredType::Struct(vec![field(
+        "a",
+        InferredType::Union(vec![
+            list(
+                InferredType::Union(vec![
+                    InferredType::Float,
+                    InferredType::String,
+                    InferredType::Boolean,
+                ]),
+                false,
+            ),
+            InferredType::Struct(vec![
+                field("b", InferredType::Integer, true),
+                field("c", InferredType::String, true),
+            ]),
+        ]),
+        false,
+    )]);
+    assert_eq!(infer_ndjson(data)?, expected);
+    Ok(())
+}
+
+#[test]
+fn repeated_keys_are_merged() -> Result<(), Error> {
+    let expected = InferredType::Struct(vec![field("a", InferredType::Float, true)]);
+    assert_eq!(
+        infer_json(&[br#"{"a": 1, "a": 2.5, "a": null}"#])?,
+        expected
+    );
+    Ok(())
+}
+
+#[test]
+fn to_value() -> Result<(), Error> {
+    let data_type = infer_ndjson(b"{\"a\": [1], \"b\": \"x\"}\n{\"b\": true}")?;
+    let expected = parse(
+        br#"{"type": "struct", "fields": [
+            {"name": "a", "nullable": true, "type": {
+                "type": "list", "item": {"name": "item", "nullable": false, "type": "integer"}
+            }},
+            {"name": "b", "nullable": false, "type": {"type": "union", "types": ["string", "boolean"]}}
+        ]}"#,
+    )?;
+    assert_eq!(data_type.to_value(), expected);
+    assert_eq!(to_string(&InferredType::Float.to_value()), r#""float""#);
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        infer_ndjson(b"{\"a\": 1}\n{\"a\": 1 2}"),
+        Err(Error::new(ErrorKind::MissingComma(b'2'), 17))
+    );
+
+    let options = ParseOptions::default().with_max_depth(1);
+    assert_eq!(
+        infer_ndjson_with_options(b"[1]\n[[1]]", &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 5))
+    );
+
+    // failed examples are not added
+    let mut inference = Inference::new();
+    inference.add_json(b"1").unwrap();
+    assert!(inference.add_json(br#"[1, "a""#).is_err());
+    assert_eq!(inference.data_type(), &InferredType::Integer);
+}
+
+proptest! {
+    #[test]
+    fn json_same_as_values(
+        examples in prop::collection::vec("[\\[\\]{}\":,0-2ab. tn]{0,12}", 0..5)
+    ) {
+        let valid = examples.iter().filter(|x| parse(x.as_bytes()).is_ok()).collect::<Vec<_>>();
+        let values = valid.iter().map(|x| parse(x.as_bytes()).unwrap()).collect::<Vec<_>>();
+        let mut inference = Inference::new();
+        for example in &valid {
+            inference.add_json(example.as_bytes()).unwrap();
+        }
+        prop_assert_eq!(sorted(inference.finish()), sorted(infer(&values)));
+    }
+}
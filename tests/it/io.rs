@@ -0,0 +1,79 @@
+use std::borrow::Cow;
+use std::io::{self, Read};
+
+use json_deserializer::{
+    from_reader, from_reader_with_options, parse, parse_with_options, Error, ErrorKind,
+    ParseOptions, Value,
+};
+
+/// A reader that returns at most 3 bytes per read
+struct Slow<'a>(&'a [u8]);
+
+impl Read for Slow<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let length = buf.len().min(3).min(self.0.len());
+        buf[..length].copy_from_slice(&self.0[..length]);
+        self.0 = &self.0[length..];
+        Ok(length)
+    }
+}
+
+fn read(json: &[u8]) -> Value<'static> {
+    from_reader(Slow(json)).unwrap()
+}
+
+#[test]
+fn owned() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": [1, "b", {"c\n": 1.5e3}], "d": null}"#;
+
+    let value = read(data);
+    assert_eq!(value, parse(data)?);
+    if let Value::Object(object) = &value {
+        assert!(matches!(object.keys().next(), Some(Cow::Owned(_))));
+        let Value::Array(items) = &object["a"] else {
+            panic!()
+        };
+        assert!(matches!(items[1], Value::String(Cow::Owned(_))));
+    } else {
+        panic!()
+    }
+
+    let data: &[u8] = b"[.5, 'a', {b: 0x10}, Infinity,]";
+    let options = ParseOptions::default().with_relaxed(true);
+    let value = from_reader_with_options(Slow(data), &options).unwrap();
+    assert_eq!(value, parse_with_options(data, &options)?);
+    Ok(())
+}
+
+#[test]
+fn invalid_json() {
+    let error = from_reader(Slow(b"[1, 2")).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    let error = error.into_inner().unwrap().downcast::<Error>().unwrap();
+    assert_eq!(*error, Error::new(ErrorKind::InvalidEOF, 5));
+
+    let options = ParseOptions::default().with_max_depth(1);
+    let error = from_reader_with_options(Slow(b"[[]]"), &options).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn io_error() {
+    struct Failing;
+    impl Read for Failing {
+        fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+            Err(io::Error::new(io::ErrorKind::ConnectionReset, "reset"))
+        }
+    }
+    let error = from_reader(Failing).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::ConnectionReset);
+}
+
+#[test]
+fn boxed_error() {
+    fn parse_boxed(json: &[u8]) -> Result<Value<'_>, Box<dyn std::error::Error>> {
+        Ok(parse(json)?)
+    }
+    let error = parse_boxed(b"[1 2]").unwrap_err();
+    assert_eq!(error.to_string(), "MissingComma(50) at byte 3");
+}
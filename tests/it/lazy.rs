@@ -0,0 +1,223 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_lazy, parse_lazy_with_options, parse_with_options, DuplicateKeys, Error,
+    ErrorKind, LazyValue, Number, ParseOptions, Value,
+};
+
+/// Converts `value` into a [`Value`] by iterating over its items
+fn to_value_by_items<'a>(value: &LazyValue<'a>) -> Result<Value<'a>, Error> {
+    if let Some(iter) = value.iter_array() {
+        iter.map(|item| to_value_by_items(&item?))
+            .collect::<Result<_, _>>()
+            .map(Value::Array)
+    } else if let Some(iter) = value.iter_object() {
+        iter.map(|item| item.and_then(|(k, v)| Ok((k, to_value_by_items(&v)?))))
+            .collect::<Result<_, _>>()
+            .map(Value::Object)
+    } else {
+        value.to_value()
+    }
+}
+
+#[test]
+fn get() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": [1, {"b": "c"}], "d": null, "e\n": true}"#;
+
+    let value = parse_lazy(data)?;
+    assert_eq!(value.span(), 0..data.len());
+
+    let a = value.get("a")?.unwrap();
+    assert_eq!(a.as_bytes(), br#"[1, {"b": "c"}]"#);
+    assert_eq!(a.span(), 6..21);
+    assert_eq!(a.get("a")?, None);
+
+    let b = a.index(1)?.unwrap().get("b")?.unwrap();
+    assert_eq!(b.to_value()?, Value::String("c".into()));
+    assert_eq!(a.index(2)?, None);
+    assert_eq!(b.index(0)?, None);
+
+    assert_eq!(value.get("d")?.unwrap().to_value()?, Value::Null);
+    assert_eq!(value.get("e\n")?.unwrap().to_value()?, Value::Bool(true));
+    assert_eq!(value.get("f")?, None);
+    Ok(())
+}
+
+#[test]
+fn last_key_wins() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": 1, "a": 2}"#;
+
+    let a = parse_lazy(data)?.get("a")?.unwrap();
+    assert_eq!(
+        a.to_value()?,
+        Value::Number(Number::Integer(b"2".into(), b"".into()))
+    );
+    Ok(())
+}
+
+#[test]
+fn iter() -> Result<(), Error> {
+    let data: &[u8] = br#" [ {"a" : 1 } , [] ,"b" ] "#;
+
+    let value = parse_lazy(data)?;
+    assert_eq!(value.span(), 1..data.len() - 1);
+    assert!(value.iter_object().is_none());
+
+    let items = value.iter_array().unwrap().collect::<Result<Vec<_>, _>>()?;
+    let items = items.iter().map(|x| x.as_bytes()).collect::<Vec<_>>();
+    assert_eq!(items, vec![&br#"{"a" : 1 }"#[..], b"[]", b"\"b\""]);
+
+    let object = value.index(0)?.unwrap();
+    let items = object
+        .iter_object()
+        .unwrap()
+        .map(|x| x.map(|(k, v)| (k, v.as_bytes())))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(items, vec![("a".into(), &b"1"[..])]);
+    Ok(())
+}
+
+#[test]
+fn to_value() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": [1, {"b": "c"}], "d": null}"#;
+
+    let value = parse_lazy(data)?;
+    let expected = parse(data)?;
+    assert_eq!(value.to_value()?, expected);
+    assert_eq!(
+        value.get("a")?.unwrap().to_value()?,
+        parse(br#"[1, {"b": "c"}]"#)?
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let cases: &[&[u8]] = &[
+        b"",
+        b"[1, 2",
+        b"[1 2]",
+        b"{\"a\" 1}",
+        b"{\"a\": 1,}",
+        b"{1: 1}",
+        b"[\"\\u12\"]",
+        b"[\"a\x01\"]",
+        b"[nul]",
+        b"[1.]",
+        b"[1] 2",
+        &[b'[', b'"', 195, 40, b'"', b']'],
+    ];
+    for data in cases {
+        assert_eq!(
+            parse_lazy(data),
+            Err(parse(data).unwrap_err()),
+            "{}",
+            String::from_utf8_lossy(data)
+        );
+    }
+}
+
+#[test]
+fn max_depth() -> Result<(), Error> {
+    let options = ParseOptions::default().with_max_depth(2);
+    let value = parse_lazy_with_options(b"[[1]]", &options)?;
+    assert_eq!(
+        value.index(0)?.unwrap().to_value()?,
+        Value::Array(vec![Value::Number(Number::Integer(
+            b"1".into(),
+            b"".into()
+        ))])
+    );
+
+    assert_eq!(
+        parse_lazy_with_options(b"[[[1]]]", &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 2))
+    );
+    Ok(())
+}
+
+#[test]
+fn duplicate_keys() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": 1, "b": {"c": 2, "c": 3}, "a": 4}"#;
+
+    let options = ParseOptions::default().with_duplicate_keys(DuplicateKeys::FirstWins);
+    let value = parse_lazy_with_options(data, &options)?;
+    assert_eq!(value.get("a")?.unwrap().as_bytes(), b"1");
+
+    // repeated keys are found when their object is accessed
+    let options = ParseOptions::default().with_duplicate_keys(DuplicateKeys::Error);
+    let value = parse_lazy_with_options(data, &options)?;
+    let error = Error::new(ErrorKind::DuplicateKey("a".to_string()), 32);
+    assert_eq!(value.get("b"), Err(error.clone()));
+    assert_eq!(value.iter_object().unwrap().last(), Some(Err(error)));
+
+    let b = value.iter_object().unwrap().nth(1).unwrap()?.1;
+    assert_eq!(
+        b.get("c"),
+        Err(Error::new(ErrorKind::DuplicateKey("c".to_string()), 23))
+    );
+    assert_eq!(
+        value.to_value(),
+        Err(Error::new(ErrorKind::DuplicateKey("c".to_string()), 23))
+    );
+
+    // items are returned without checking their keys
+    let value = parse_lazy_with_options(br#"[{"c": 2, "c": 3}]"#, &options)?;
+    let item = value.index(0)?.unwrap();
+    assert_eq!(
+        item.to_value(),
+        Err(Error::new(ErrorKind::DuplicateKey("c".to_string()), 10))
+    );
+    Ok(())
+}
+
+#[test]
+fn relaxed() -> Result<(), Error> {
+    let data: &[u8] = br#"{
+        // a "comment" with ] and }
+        a: ['"', "'", /* ] */ "\"", [-.5, +1,], 0x1F, Infinity],
+        'b': {c: null, /* } */},
+        d: true
+    }"#;
+    let options = ParseOptions::default().with_relaxed(true);
+
+    let value = parse_lazy_with_options(data, &options)?;
+    let a = value.get("a")?.unwrap();
+    assert_eq!(a.iter_array().unwrap().count(), 6);
+    assert_eq!(a.index(3)?.unwrap().as_bytes(), b"[-.5, +1,]");
+    assert_eq!(a.index(5)?.unwrap().as_bytes(), b"Infinity");
+    assert_eq!(value.get("b")?.unwrap().as_bytes(), b"{c: null, /* } */}");
+    assert_eq!(value.get("d")?.unwrap().to_value()?, Value::Bool(true));
+    assert_eq!(
+        to_value_by_items(&value)?,
+        parse_with_options(data, &options)?
+    );
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn same_errors_as_parse(data in "[\\[\\]{}\":,0-9a-z\\\\ .-]{0,20}") {
+        match parse(data.as_bytes()) {
+            Ok(expected) => prop_assert_eq!(parse_lazy(data.as_bytes()).and_then(|value| value.to_value()), Ok(expected)),
+            Err(e) => prop_assert_eq!(parse_lazy(data.as_bytes()), Err(e)),
+        }
+    }
+
+    #[test]
+    fn items_same_as_parse(data in "[\\[\\]{}\":,0-9a-z\\\\ .-]{0,20}") {
+        if let Ok(expected) = parse(data.as_bytes()) {
+            let value = parse_lazy(data.as_bytes()).unwrap();
+            prop_assert_eq!(to_value_by_items(&value).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn items_same_as_parse_relaxed(data in "[\\[\\]{}\"':,0-9a-z/*\\\\ \n.+-]{0,20}") {
+        let options = ParseOptions::default().with_relaxed(true);
+        if let Ok(expected) = parse_with_options(data.as_bytes(), &options) {
+            let value = parse_lazy_with_options(data.as_bytes(), &options).unwrap();
+            prop_assert_eq!(to_value_by_items(&value).unwrap(), expected);
+        }
+    }
+}
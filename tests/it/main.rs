@@ -1,10 +1,35 @@
+#[cfg(feature = "arrow")]
+mod arrow;
+mod duplicate_keys;
+mod incremental;
+mod infer;
+#[cfg(feature = "std")]
+mod io;
 mod json_integration;
+mod lazy;
+mod ndjson;
+mod number;
+mod pointer;
+mod projection;
+mod reader;
+mod relaxed;
+mod schema;
+#[cfg(feature = "serde")]
+mod serde;
+mod stream;
+mod string;
+mod tape;
+mod validate;
+mod visitor;
+mod writer;
 
 use std::borrow::Cow;
 
-use json_deserializer::{parse, Error, Number, Object, Value};
+use json_deserializer::{
+    parse, parse_with_options, position, Error, ErrorKind, Number, Object, ParseOptions, Value,
+};
 
-fn string(v: &str) -> Cow<str> {
+fn string(v: &str) -> Cow<'_, str> {
     Cow::Borrowed(v)
 }
 
@@ -25,7 +50,10 @@ fn basics() -> Result<(), Error> {
     let d = [
         (string("a"), Value::String(string("b"))),
         (string("b"), Value::String(string("c"))),
-        (string("c"), Value::Number(Number::Float(b"1.1", b""))),
+        (
+            string("c"),
+            Value::Number(Number::Float(b"1.1".into(), b"".into())),
+        ),
         (string("d"), Value::Null),
         (string("e"), Value::Bool(false)),
         (string("f"), Value::Bool(true)),
@@ -33,7 +61,7 @@ fn basics() -> Result<(), Error> {
             string("g"),
             Value::Array(vec![
                 Value::String(string("b")),
-                Value::Number(Number::Integer(b"2", b"")),
+                Value::Number(Number::Integer(b"2".into(), b"".into())),
                 Value::Null,
                 Value::Bool(true),
                 Value::Bool(false),
@@ -43,7 +71,6 @@ fn basics() -> Result<(), Error> {
         ),
     ]
     .into_iter()
-    .map(|(key, value)| (key.into_owned(), value))
     .collect::<Object>();
 
     assert_eq!(item, Value::Object(d));
@@ -60,7 +87,7 @@ fn comma_and_string() -> Result<(), Error> {
         Value::Array(vec![
             Value::String(string(",")),
             Value::String(string("1.2")),
-            Value::Number(Number::Float(b"1.2", b""))
+            Value::Number(Number::Float(b"1.2".into(), b"".into()))
         ])
     );
     Ok(())
@@ -70,15 +97,28 @@ fn comma_and_string() -> Result<(), Error> {
 fn empty_object() -> Result<(), Error> {
     let data: &[u8] = b"[{\"\":null}]";
 
-    let o = [(string("").into_owned(), Value::Null)]
-        .into_iter()
-        .collect::<Object>();
+    let o = [(string(""), Value::Null)].into_iter().collect::<Object>();
 
     let item = parse(data)?;
     assert_eq!(item, Value::Array(vec![Value::Object(o)]));
     Ok(())
 }
 
+#[test]
+fn borrowed_keys() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": 1, "\n": 2}"#;
+
+    let item = parse(data)?;
+    if let Value::Object(object) = item {
+        let keys = object.keys().collect::<Vec<_>>();
+        assert!(keys.iter().any(|k| matches!(k, Cow::Borrowed("a"))));
+        assert!(keys.iter().any(|k| matches!(k, Cow::Owned(k) if k == "\n")));
+    } else {
+        panic!()
+    }
+    Ok(())
+}
+
 #[test]
 fn escaped() -> Result<(), Error> {
     let data: &[u8] = br#"["\n"]"#;
@@ -116,11 +156,14 @@ fn number() -> Result<(), Error> {
 
     assert_eq!(
         parse(num.as_bytes())?,
-        Value::Number(Number::Integer(b"10", b""))
+        Value::Number(Number::Integer(b"10".into(), b"".into()))
     );
     assert_eq!(
         parse(array.as_bytes())?,
-        Value::Array(vec![Value::Number(Number::Integer(b"10", b""))])
+        Value::Array(vec![Value::Number(Number::Integer(
+            b"10".into(),
+            b"".into()
+        ))])
     );
     Ok(())
 }
@@ -185,11 +228,14 @@ fn number_exponent() -> Result<(), Error> {
 
     assert_eq!(
         parse(num.as_bytes())?,
-        Value::Number(Number::Integer(b"1", b"10"))
+        Value::Number(Number::Integer(b"1".into(), b"10".into()))
     );
     assert_eq!(
         parse(array.as_bytes())?,
-        Value::Array(vec![Value::Number(Number::Integer(b"1", b"10"))])
+        Value::Array(vec![Value::Number(Number::Integer(
+            b"1".into(),
+            b"10".into()
+        ))])
     );
     Ok(())
 }
@@ -201,11 +247,14 @@ fn number_exponent1() -> Result<(), Error> {
 
     assert_eq!(
         parse(num.as_bytes())?,
-        Value::Number(Number::Float(b"1", b"-42"))
+        Value::Number(Number::Float(b"1".into(), b"-42".into()))
     );
     assert_eq!(
         parse(array.as_bytes())?,
-        Value::Array(vec![Value::Number(Number::Float(b"1", b"-42"))])
+        Value::Array(vec![Value::Number(Number::Float(
+            b"1".into(),
+            b"-42".into()
+        ))])
     );
     Ok(())
 }
@@ -217,13 +266,13 @@ fn number_exponent2() -> Result<(), Error> {
 
     assert_eq!(
         parse(num.as_bytes())?,
-        Value::Number(Number::Float(b"8.310346185542391", b"275"))
+        Value::Number(Number::Float(b"8.310346185542391".into(), b"275".into()))
     );
     assert_eq!(
         parse(array.as_bytes())?,
         Value::Array(vec![Value::Number(Number::Float(
-            b"8.310346185542391",
-            b"275"
+            b"8.310346185542391".into(),
+            b"275".into()
         ))])
     );
     Ok(())
@@ -237,16 +286,19 @@ fn number_exponent3() -> Result<(), Error> {
 
     assert_eq!(
         parse(num.as_bytes())?,
-        Value::Number(Number::Float(b"1.1", b"+10"))
+        Value::Number(Number::Float(b"1.1".into(), b"+10".into()))
     );
     assert_eq!(
         parse(array.as_bytes())?,
-        Value::Array(vec![Value::Number(Number::Float(b"1.1", b"+10"))])
+        Value::Array(vec![Value::Number(Number::Float(
+            b"1.1".into(),
+            b"+10".into()
+        ))])
     );
     let mut expected = Object::new();
     expected.insert(
-        "Value".to_string(),
-        Value::Number(Number::Float(b"1.1", b"+10")),
+        string("Value"),
+        Value::Number(Number::Float(b"1.1".into(), b"+10".into())),
     );
 
     assert_eq!(parse(obj.as_bytes())?, Value::Object(expected));
@@ -262,6 +314,27 @@ fn pretty_1() -> Result<(), Error> {
     Ok(())
 }
 
+#[test]
+fn whitespace() -> Result<(), Error> {
+    for length in 0..70 {
+        let space = " \n\r\t".repeat(length / 4) + &" ".repeat(length % 4);
+        let data = format!("{0}[{0}1{0},{0}{{{0}\"a\"{0}:{0}null{0}}}{0}]{0}", space);
+        assert_eq!(
+            parse(data.as_bytes())?,
+            parse(br#"[1, {"a": null}]"#)?,
+            "{:?}",
+            data
+        );
+    }
+
+    let data = format!("[1{}2]", " ".repeat(40));
+    assert_eq!(
+        parse(data.as_bytes()),
+        Err(Error::new(ErrorKind::MissingComma(b'2'), 42))
+    );
+    Ok(())
+}
+
 #[test]
 fn edges() {
     assert!(parse(br#""#).is_err());
@@ -271,6 +344,10 @@ fn edges() {
     assert!(parse(br#""\u""#).is_err());
     assert!(parse(br#""\u"""#).is_err());
     assert!(parse(br#""\u1234""#).is_ok());
+    assert!(parse(br#""\u123"#).is_err());
+    assert!(parse(br#""\ud800\n""#).is_err());
+    assert!(parse(br#""\ud800\"""#).is_err());
+    assert!(parse(br#""\ud800\udc00""#).is_ok());
 
     assert!(parse(br#"1"#).is_ok());
     assert!(parse(br#"11"#).is_ok());
@@ -319,7 +396,7 @@ fn json_org_validator_tests() {
     assert!(parse(include_bytes!("json_org_validator_tests/fail15.json")).is_err());
     assert!(parse(include_bytes!("json_org_validator_tests/fail16.json")).is_err());
     assert!(parse(include_bytes!("json_org_validator_tests/fail17.json")).is_err());
-    //assert!(parse(include_bytes!("json_org_validator_tests/fail18.json")).is_err()); // No maximum depth
+    //assert!(parse(include_bytes!("json_org_validator_tests/fail18.json")).is_err()); // Default maximum depth is above 20, see `max_depth`
     assert!(parse(include_bytes!("json_org_validator_tests/fail19.json")).is_err());
     assert!(parse(include_bytes!("json_org_validator_tests/fail20.json")).is_err());
     assert!(parse(include_bytes!("json_org_validator_tests/fail21.json")).is_err());
@@ -340,9 +417,99 @@ fn json_org_validator_tests() {
 #[test]
 fn err_fmt() {
     let er = parse(br#"paa"#).err().unwrap();
-    assert_eq!(format!("{}", er), "InvalidToken(112)".to_string());
-    assert_eq!(format!("{:?}", er), "InvalidToken(112)".to_string());
-    assert_eq!(er, Error::InvalidToken(112))
+    assert_eq!(format!("{}", er), "InvalidToken(112) at byte 0".to_string());
+    assert_eq!(
+        format!("{:?}", er),
+        "Error { kind: InvalidToken(112), offset: 0 }".to_string()
+    );
+    assert_eq!(er, Error::new(ErrorKind::InvalidToken(112), 0))
+}
+
+#[test]
+fn err_offset() {
+    let cases: &[(&[u8], ErrorKind, usize)] = &[
+        (b"", ErrorKind::InvalidEOF, 0),
+        (b"[1, 2 3]", ErrorKind::MissingComma(b'3'), 6),
+        (b"[1, 2] 3", ErrorKind::InvalidEOF, 7),
+        (b"[1, 2", ErrorKind::InvalidEOF, 5),
+        (br#"{"a" 1}"#, ErrorKind::InvalidToken(b'1'), 5),
+        (br#"{"a": 1, 2}"#, ErrorKind::InvalidStringToken(b'2'), 9),
+        (b"[true, trua]", ErrorKind::InvalidTrueToken(*b"trua"), 7),
+        (b"[nul", ErrorKind::InvalidEOF, 1),
+        (b"[01]", ErrorKind::NumberWithLeadingZero, 2),
+        (b"[1.]", ErrorKind::NumberWithEmptyFraction, 3),
+        (b"[\"a\tb\"]", ErrorKind::StringWithControlCharacters, 3),
+        (br#"["ab\x"]"#, ErrorKind::InvalidEscaped(b'x'), 4),
+        (br#"["\u12"#, ErrorKind::InvalidEOF, 6),
+        (
+            &[b'[', b'"', b'a', 195, 40, b'"', b']'],
+            ErrorKind::InvalidUtf8,
+            3,
+        ),
+    ];
+    for (data, kind, offset) in cases {
+        let er = parse(data).err().unwrap();
+        assert_eq!(er, Error::new(kind.clone(), *offset), "{:?}", data);
+    }
+}
+
+#[test]
+fn err_position() {
+    let data: &[u8] = b"{\n  \"a\": 1,\n  \"b\": [1, 2 3]\n}";
+    let er = parse(data).err().unwrap();
+    let pos = er.position(data);
+    assert_eq!(pos.line, 3);
+    assert_eq!(pos.column, 14);
+    assert_eq!(pos.excerpt, "  \"b\": [1, 2 3]");
+
+    let data = "\"é\n\"".as_bytes();
+    let er = parse(data).err().unwrap();
+    assert_eq!(position(data, er.offset()), er.position(data));
+    assert_eq!((er.position(data).line, er.position(data).column), (1, 3));
+
+    let data = "[1, \"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa\", 3 4]";
+    let er = parse(data.as_bytes()).err().unwrap();
+    assert_eq!(
+        position(data.as_bytes(), er.offset()).excerpt,
+        "aaaaaaaaaaaaaaa\", 3 4]"
+    );
+}
+
+#[test]
+fn max_depth() -> Result<(), Error> {
+    let options = ParseOptions::default().with_max_depth(2);
+    assert!(parse_with_options(b"[[1], {}]", &options).is_ok());
+    assert!(parse_with_options(br#"{"a": {"b": 1}}"#, &options).is_ok());
+    assert_eq!(
+        parse_with_options(b"[[[1]]]", &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 2))
+    );
+    assert_eq!(
+        parse_with_options(br#"{"a": [{}]}"#, &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 7))
+    );
+
+    let options = ParseOptions::default().with_max_depth(0);
+    assert!(parse_with_options(b"1", &options).is_ok());
+    assert!(parse_with_options(b"[]", &options).is_err());
+
+    // fail18.json is nested 20 levels deep
+    let data = include_bytes!("json_org_validator_tests/fail18.json");
+    assert!(parse_with_options(data, &ParseOptions::default().with_max_depth(19)).is_err());
+    assert!(parse_with_options(data, &ParseOptions::default().with_max_depth(20)).is_ok());
+    Ok(())
+}
+
+#[test]
+fn deep_nesting_does_not_overflow() {
+    let data = "[".repeat(1_000_000);
+    assert_eq!(
+        parse(data.as_bytes()),
+        Err(Error::new(
+            ErrorKind::RecursionLimitExceeded,
+            ParseOptions::default().max_depth
+        ))
+    );
 }
 
 #[test]
@@ -354,7 +521,7 @@ fn surrogates() {
     assert!(parse(br#""\uD83C\uDF95""#).is_ok());
     assert!(parse(br#""\uD83C\uFFFF""#).is_err());
     assert!(parse(br#""\uD83C\FDF95""#).is_err());
-    assert!(parse(br#""\uD83C\\""#).is_ok());
+    assert!(parse(br#""\uD83C\\""#).is_err());
     assert!(parse(br#""\uDB00""#).is_err());
     assert!(parse(br#""\uFFFF""#).is_ok());
     assert!(parse(br#""\u\FFFF""#).is_err());
@@ -381,13 +548,18 @@ fn object() -> Result<(), Error> {
     let item = parse(data)?;
 
     let d = [
-        (string("u64"), Value::Number(Number::Integer(b"1", b""))),
-        (string("f64"), Value::Number(Number::Float(b"0.1", b""))),
+        (
+            string("u64"),
+            Value::Number(Number::Integer(b"1".into(), b"".into())),
+        ),
+        (
+            string("f64"),
+            Value::Number(Number::Float(b"0.1".into(), b"".into())),
+        ),
         (string("utf8"), Value::String(string("foo1"))),
         (string("bools"), Value::Bool(true)),
     ]
     .into_iter()
-    .map(|(key, value)| (key.into_owned(), value))
     .collect::<Object>();
 
     assert_eq!(item, Value::Object(d));
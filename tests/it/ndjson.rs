@@ -0,0 +1,79 @@
+use json_deserializer::{
+    parse_ndjson, parse_ndjson_with_options, Error, ErrorKind, NdjsonOptions, Number, Value,
+};
+
+#[test]
+fn basics() -> Result<(), Error> {
+    let data: &[u8] = b"{\"a\": 1}\n[true]\r\n\"a\"\n";
+
+    let items = parse_ndjson(data).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[1], Value::Array(vec![Value::Bool(true)]));
+    assert_eq!(items[2], Value::String("a".into()));
+    Ok(())
+}
+
+#[test]
+fn no_trailing_newline() -> Result<(), Error> {
+    let data: &[u8] = b"1\n2";
+
+    let items = parse_ndjson(data).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        items,
+        vec![
+            Value::Number(Number::Integer(b"1".into(), b"".into())),
+            Value::Number(Number::Integer(b"2".into(), b"".into()))
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn error_line() {
+    let data: &[u8] = b"1\n2\n[1 2]\n3\n";
+
+    let mut iter = parse_ndjson(data);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    let error = iter.next().unwrap().unwrap_err();
+    assert_eq!(iter.line(), 3);
+    assert_eq!(error, Error::new(ErrorKind::MissingComma(b'2'), 7));
+    assert_eq!(error.position(data).line, 3);
+    // stops after the first error by default
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn multiple_values_in_line() {
+    let data: &[u8] = b"1 2\n";
+
+    let error = parse_ndjson(data).next().unwrap().unwrap_err();
+    assert_eq!(error, Error::new(ErrorKind::InvalidEOF, 2));
+}
+
+#[test]
+fn blank_lines() {
+    let data: &[u8] = b"1\n\n  \n2\n";
+
+    let items = parse_ndjson(data).collect::<Vec<_>>();
+    assert_eq!(items.len(), 2);
+    assert!(items[1].is_err());
+
+    let options = NdjsonOptions::default().with_skip_blank_lines(true);
+    let mut iter = parse_ndjson_with_options(data, options);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(iter.line(), 4);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn continue_on_error() {
+    let data: &[u8] = b"1\nnul\n2\n{\n3";
+
+    let options = NdjsonOptions::default().with_continue_on_error(true);
+    let items = parse_ndjson_with_options(data, options)
+        .map(|x| x.is_ok())
+        .collect::<Vec<_>>();
+    assert_eq!(items, vec![true, false, true, false, true]);
+}
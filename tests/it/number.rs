@@ -0,0 +1,171 @@
+use proptest::prelude::*;
+
+use json_deserializer::{parse, validate, Error, ErrorKind, Number, Value};
+
+fn number(data: &str) -> Number<'_> {
+    match parse(data.as_bytes()).unwrap() {
+        Value::Number(number) => number,
+        _ => panic!(),
+    }
+}
+
+#[test]
+fn lone_sign() {
+    let cases: &[(&[u8], ErrorKind, usize)] = &[
+        (b"-", ErrorKind::InvalidEOF, 1),
+        (b"[-]", ErrorKind::InvalidToken(b']'), 2),
+        (b"[-, 1]", ErrorKind::InvalidToken(b','), 2),
+        (br#"{"a": - 1}"#, ErrorKind::InvalidToken(b' '), 7),
+        (b"-a", ErrorKind::InvalidToken(b'a'), 1),
+    ];
+    for (data, kind, offset) in cases {
+        let expected = Err(Error::new(kind.clone(), *offset));
+        assert_eq!(parse(data), expected, "{:?}", data);
+        assert_eq!(validate(data), expected.map(|_: Value| ()), "{:?}", data);
+    }
+}
+
+#[test]
+fn to_i64() {
+    let cases: &[(&str, Result<i64, ErrorKind>)] = &[
+        ("0", Ok(0)),
+        ("-0", Ok(0)),
+        ("123", Ok(123)),
+        ("-123", Ok(-123)),
+        ("1e3", Ok(1000)),
+        ("1E+3", Ok(1000)),
+        ("1.5e1", Ok(15)),
+        ("1.0", Ok(1)),
+        ("100e-2", Ok(1)),
+        ("0e-5", Ok(0)),
+        ("0.000", Ok(0)),
+        ("0e99999999999999999999", Ok(0)),
+        ("9223372036854775807", Ok(i64::MAX)),
+        ("-9223372036854775808", Ok(i64::MIN)),
+        ("9223372036854775808", Err(ErrorKind::NumberOverflow)),
+        ("-9223372036854775809", Err(ErrorKind::NumberOverflow)),
+        ("1e100", Err(ErrorKind::NumberOverflow)),
+        ("1e99999999999999999999", Err(ErrorKind::NumberOverflow)),
+        ("1.5", Err(ErrorKind::NumberPrecisionLoss)),
+        ("1.50", Err(ErrorKind::NumberPrecisionLoss)),
+        ("1e-1", Err(ErrorKind::NumberPrecisionLoss)),
+        ("15e-1", Err(ErrorKind::NumberPrecisionLoss)),
+    ];
+    for (data, expected) in cases {
+        assert_eq!(number(data).to_i64(), *expected, "{}", data);
+    }
+}
+
+#[test]
+fn to_u64() {
+    assert_eq!(number("18446744073709551615").to_u64(), Ok(u64::MAX));
+    assert_eq!(
+        number("18446744073709551616").to_u64(),
+        Err(ErrorKind::NumberOverflow)
+    );
+    assert_eq!(number("-0").to_u64(), Ok(0));
+    assert_eq!(number("-1").to_u64(), Err(ErrorKind::NumberOverflow));
+    assert_eq!(number("2.5e1").to_u64(), Ok(25));
+    assert_eq!(
+        number("2.55e1").to_u64(),
+        Err(ErrorKind::NumberPrecisionLoss)
+    );
+}
+
+#[test]
+fn to_i128() {
+    let max = i128::MAX.to_string();
+    let min = i128::MIN.to_string();
+    assert_eq!(number(&max).to_i128(), Ok(i128::MAX));
+    assert_eq!(number(&min).to_i128(), Ok(i128::MIN));
+    assert_eq!(
+        number("170141183460469231731687303715884105728").to_i128(),
+        Err(ErrorKind::NumberOverflow)
+    );
+    assert_eq!(number("1e38").to_i128(), Ok(10i128.pow(38)));
+    assert_eq!(number("1e39").to_i128(), Err(ErrorKind::NumberOverflow));
+}
+
+#[test]
+fn to_u128() {
+    let max = u128::MAX.to_string();
+    assert_eq!(number(&max).to_u128(), Ok(u128::MAX));
+    assert_eq!(
+        number("340282366920938463463374607431768211456").to_u128(),
+        Err(ErrorKind::NumberOverflow)
+    );
+    assert_eq!(number("-0").to_u128(), Ok(0));
+    assert_eq!(number("-1").to_u128(), Err(ErrorKind::NumberOverflow));
+}
+
+#[test]
+fn to_f64() {
+    assert_eq!(number("0").to_f64(), Ok(0.0));
+    assert!(number("-0.0").to_f64().unwrap().is_sign_negative());
+    assert_eq!(number("1").to_f64(), Ok(1.0));
+    assert_eq!(number("-1.5e-3").to_f64(), Ok(-0.0015));
+    assert_eq!(number("0.1").to_f64(), Ok(0.1));
+    assert_eq!(number("1.7976931348623157e308").to_f64(), Ok(f64::MAX));
+    assert_eq!(number("1e400").to_f64(), Err(ErrorKind::NumberOverflow));
+    assert_eq!(
+        number("1e-400").to_f64(),
+        Err(ErrorKind::NumberPrecisionLoss)
+    );
+    assert_eq!(number("0e-400").to_f64(), Ok(0.0));
+    assert_eq!(
+        number("5e-324").to_f64(),
+        Ok(f64::from_bits(1)) // smallest subnormal
+    );
+}
+
+#[test]
+fn to_f64_many_digits() {
+    // exactly halfway between 2^53 and 2^53 + 2 rounds to even...
+    assert_eq!(number("9007199254740993").to_f64(), Ok(9007199254740992.0));
+    // ...but anything above it rounds up, even beyond 768 digits
+    let data = format!("9007199254740993.{}1", "0".repeat(800));
+    assert_eq!(number(&data).to_f64(), Ok(9007199254740994.0));
+    let data = format!("9007199254740993{}e-900", "0".repeat(900));
+    assert_eq!(number(&data).to_f64(), Ok(9007199254740992.0));
+}
+
+#[test]
+fn to_f32() {
+    assert_eq!(number("0.1").to_f32(), Ok(0.1f32));
+    assert_eq!(number("3.4028235e38").to_f32(), Ok(f32::MAX));
+    assert_eq!(number("1e39").to_f32(), Err(ErrorKind::NumberOverflow));
+    assert_eq!(
+        number("1e-50").to_f32(),
+        Err(ErrorKind::NumberPrecisionLoss)
+    );
+}
+
+proptest! {
+    #[test]
+    fn to_f64_is_correctly_rounded(
+        data in "-?(0|[1-9][0-9]{0,30})(\\.[0-9]{1,30})?([eE][+-]?[0-9]{1,3})?"
+    ) {
+        let expected = data.parse::<f64>().unwrap();
+        match number(&data).to_f64() {
+            Ok(result) => prop_assert_eq!(result.to_bits(), expected.to_bits()),
+            Err(ErrorKind::NumberOverflow) => prop_assert!(expected.is_infinite()),
+            Err(e) => {
+                prop_assert_eq!(e, ErrorKind::NumberPrecisionLoss);
+                prop_assert_eq!(expected, 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn f64_roundtrip(value in any::<f64>().prop_filter("finite", |x| x.is_finite())) {
+        let data = format!("{:e}", value);
+        prop_assert_eq!(number(&data).to_f64(), Ok(value));
+    }
+
+    #[test]
+    fn i64_roundtrip(value in any::<i64>()) {
+        let data = value.to_string();
+        prop_assert_eq!(number(&data).to_i64(), Ok(value));
+        prop_assert_eq!(number(&format!("{}.000e0", value)).to_i64(), Ok(value));
+    }
+}
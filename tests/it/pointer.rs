@@ -0,0 +1,89 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_pointer, parse_pointer_with_options, Error, ErrorKind, ParseOptions, Value,
+};
+
+const DATA: &[u8] = br#"{
+    "a": [{"b": 1}, [true, null]],
+    "c/d": "e",
+    "f~g": "h",
+    "": 0,
+    " ": 1,
+    "a\nb": "i",
+    "10": 2,
+    "dup": 1,
+    "dup": [3]
+}"#;
+
+#[test]
+fn pointer() -> Result<(), Error> {
+    let value = parse(DATA)?;
+    let cases: &[(&str, Option<&[u8]>)] = &[
+        ("", Some(DATA)),
+        ("/a", Some(br#"[{"b": 1}, [true, null]]"#)),
+        ("/a/0", Some(br#"{"b": 1}"#)),
+        ("/a/0/b", Some(b"1")),
+        ("/a/1/1", Some(b"null")),
+        ("/c~1d", Some(b"\"e\"")),
+        ("/f~0g", Some(b"\"h\"")),
+        ("/", Some(b"0")),
+        ("/ ", Some(b"1")),
+        ("/a\nb", Some(b"\"i\"")),
+        ("/10", Some(b"2")),
+        ("/dup/0", Some(b"3")),
+        // not found
+        ("/x", None),
+        ("/a/2", None),
+        ("/a/-", None),
+        ("/a/00", None),
+        ("/a/+0", None),
+        ("/a/0/b/c", None),
+        ("/c~1d/0", None),
+        // invalid pointers
+        ("a", None),
+        ("/f~2g", None),
+        ("/f~", None),
+    ];
+    for (pointer, expected) in cases {
+        let expected = expected.map(|x| parse(x).unwrap());
+        assert_eq!(value.pointer(pointer), expected.as_ref(), "{}", pointer);
+        assert_eq!(parse_pointer(DATA, pointer)?, expected, "{}", pointer);
+    }
+    Ok(())
+}
+
+#[test]
+fn pointer_mut() -> Result<(), Error> {
+    let mut value = parse(DATA)?;
+    *value.pointer_mut("/a/1/0").unwrap() = Value::Bool(false);
+    assert_eq!(value.pointer("/a/1"), Some(&parse(b"[false, null]")?));
+    assert_eq!(value.pointer_mut("/a/2"), None);
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let data: &[u8] = br#"{"a": 1, "b": [1 2]}"#;
+    let expected = Err(Error::new(ErrorKind::MissingComma(b'2'), 17));
+    assert_eq!(parse_pointer(data, "/a"), expected);
+    assert_eq!(parse_pointer(data, "/b"), expected);
+    assert_eq!(parse_pointer(data, "invalid"), expected);
+
+    let options = ParseOptions::default().with_max_depth(1);
+    assert_eq!(
+        parse_pointer_with_options(br#"{"a": 1, "b": []}"#, "/a", &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 14))
+    );
+}
+
+proptest! {
+    #[test]
+    fn same_as_parse(
+        data in "[\\[\\]{}\":,0-2ab ]{0,20}",
+        pointer in "(/[0-2ab]{0,2}){0,3}"
+    ) {
+        let expected = parse(data.as_bytes()).map(|value| value.pointer(&pointer).cloned());
+        prop_assert_eq!(parse_pointer(data.as_bytes(), &pointer), expected);
+    }
+}
@@ -0,0 +1,106 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_projected, parse_projected_with_options, Error, ErrorKind, ParseOptions,
+    Projection, Value,
+};
+
+#[test]
+fn projects_fields() -> Result<(), Error> {
+    let data: &[u8] = br#"[
+        {"a": 1, "b": {"c": [1, 2], "d": "e", "f": {"g": null}}, "h": "i"},
+        {"a": 2, "b": [{"c": 3, "x": 4}, 5], "h": [{"a": 1}]},
+        {"b": 6}
+    ]"#;
+
+    let projection = Projection::new([vec!["a"], vec!["b", "c"], vec!["b", "f"]]);
+    let value = parse_projected(data, &projection)?;
+    let expected = parse(
+        br#"[
+        {"a": 1, "b": {"c": [1, 2], "f": {"g": null}}},
+        {"a": 2, "b": [{"c": 3}, 5]},
+        {"b": 6}
+    ]"#,
+    )?;
+    assert_eq!(value, expected);
+    Ok(())
+}
+
+#[test]
+fn prefix_paths() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": {"b": 1, "c": 2}, "d": 3}"#;
+
+    let projection = Projection::new([vec!["a", "b"], vec!["a"]]);
+    assert_eq!(
+        parse_projected(data, &projection)?,
+        parse(br#"{"a": {"b": 1, "c": 2}}"#)?
+    );
+
+    let projection = Projection::new([vec!["a"], vec!["a", "b"]]);
+    assert_eq!(
+        parse_projected(data, &projection)?,
+        parse(br#"{"a": {"b": 1, "c": 2}}"#)?
+    );
+
+    // the empty path selects everything
+    let projection = Projection::new([Vec::<String>::new()]);
+    assert_eq!(parse_projected(data, &projection)?, parse(data)?);
+
+    let projection = Projection::new(Vec::<Vec<String>>::new());
+    assert_eq!(parse_projected(data, &projection)?, parse(b"{}")?);
+    Ok(())
+}
+
+#[test]
+fn escaped_keys() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a\nb": 1, "a\\nb": 2}"#;
+
+    let projection = Projection::new([["a\nb"]]);
+    assert_eq!(
+        parse_projected(data, &projection)?,
+        parse(br#"{"a\nb": 1}"#)?
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let projection = Projection::new([["a"]]);
+    let data: &[u8] = br#"{"a": 1, "b": [1 2]}"#;
+    assert_eq!(
+        parse_projected(data, &projection),
+        Err(Error::new(ErrorKind::MissingComma(b'2'), 17))
+    );
+
+    let options = ParseOptions::default().with_max_depth(1);
+    assert_eq!(
+        parse_projected_with_options(br#"{"a": 1, "b": []}"#, &projection, &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 14))
+    );
+}
+
+/// Removes the fields of objects in `value` whose keys are not in `keys`
+fn project<'a>(value: Value<'a>, keys: &[&str]) -> Value<'a> {
+    match value {
+        Value::Object(object) => Value::Object(
+            object
+                .into_iter()
+                .filter(|(key, _)| keys.contains(&key.as_ref()))
+                .collect(),
+        ),
+        Value::Array(array) => Value::Array(array.into_iter().map(|x| project(x, keys)).collect()),
+        other => other,
+    }
+}
+
+proptest! {
+    #[test]
+    fn same_as_parse(
+        data in "[\\[\\]{}\":,0-2ab ]{0,20}",
+        keys in prop::sample::subsequence(vec!["a", "b", "0"], 0..3)
+    ) {
+        let projection = Projection::new(keys.iter().map(|key| [*key]));
+        let expected = parse(data.as_bytes()).map(|value| project(value, &keys));
+        prop_assert_eq!(parse_projected(data.as_bytes(), &projection), expected);
+    }
+}
@@ -0,0 +1,157 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_with_options, Error, ErrorKind, Event, Number, Object, ParseOptions, Reader, Value,
+};
+
+/// Builds a [`Value`] out of the events of a [`Reader`]
+fn to_value(json: &[u8]) -> Result<Value<'_>, Error> {
+    to_value_with_options(json, &ParseOptions::default())
+}
+
+fn to_value_with_options<'a>(json: &'a [u8], options: &ParseOptions) -> Result<Value<'a>, Error> {
+    let mut stack: Vec<(Value, Option<String>)> = vec![];
+    let mut key = None;
+    let mut result = None;
+    for event in Reader::with_options(json, options.clone()) {
+        let value = match event? {
+            Event::StartObject => {
+                stack.push((Value::Object(Object::new()), key.take()));
+                continue;
+            }
+            Event::StartArray => {
+                stack.push((Value::Array(vec![]), key.take()));
+                continue;
+            }
+            Event::Key(k) => {
+                key = Some(k.into_owned());
+                continue;
+            }
+            Event::EndObject | Event::EndArray => {
+                let (value, parent_key) = stack.pop().unwrap();
+                key = parent_key;
+                value
+            }
+            Event::String(string) => Value::String(string),
+            Event::Number(number) => Value::Number(number),
+            Event::Bool(bool) => Value::Bool(bool),
+            Event::Null => Value::Null,
+        };
+        match stack.last_mut() {
+            Some((Value::Array(array), _)) => array.push(value),
+            Some((Value::Object(object), _)) => {
+                object.insert(key.take().unwrap().into(), value);
+            }
+            Some(_) => unreachable!(),
+            None => result = Some(value),
+        }
+    }
+    Ok(result.unwrap())
+}
+
+#[test]
+fn events() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": [1, "b", {}], "c\n": null, "d": [true, false]}"#;
+
+    let events = Reader::new(data).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        events,
+        vec![
+            Event::StartObject,
+            Event::Key("a".into()),
+            Event::StartArray,
+            Event::Number(Number::Integer(b"1".into(), b"".into())),
+            Event::String("b".into()),
+            Event::StartObject,
+            Event::EndObject,
+            Event::EndArray,
+            Event::Key("c\n".into()),
+            Event::Null,
+            Event::Key("d".into()),
+            Event::StartArray,
+            Event::Bool(true),
+            Event::Bool(false),
+            Event::EndArray,
+            Event::EndObject,
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn scalar() -> Result<(), Error> {
+    let events = Reader::new(b" \"a\" ").collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(events, vec![Event::String("a".into())]);
+    Ok(())
+}
+
+#[test]
+fn depth_and_offset() -> Result<(), Error> {
+    let mut reader = Reader::new(b"[[1]]");
+    assert_eq!(reader.depth(), 0);
+    reader.next().unwrap()?;
+    reader.next().unwrap()?;
+    assert_eq!(reader.depth(), 2);
+    assert_eq!(reader.byte_offset(), 2);
+    Ok(())
+}
+
+#[test]
+fn stops_after_error() {
+    let data: &[u8] = b"[1, 2 3]";
+
+    let mut reader = Reader::new(data);
+    assert_eq!(reader.next(), Some(Ok(Event::StartArray)));
+    assert_eq!(
+        reader.next(),
+        Some(Ok(Event::Number(Number::Integer(b"1".into(), b"".into()))))
+    );
+    assert_eq!(
+        reader.next(),
+        Some(Ok(Event::Number(Number::Integer(b"2".into(), b"".into()))))
+    );
+    assert_eq!(
+        reader.next(),
+        Some(Err(Error::new(ErrorKind::MissingComma(b'3'), 6)))
+    );
+    assert_eq!(reader.next(), None);
+}
+
+#[test]
+fn max_depth() {
+    let reader = Reader::with_options(b"[[[1]]]", ParseOptions::default().with_max_depth(2));
+    assert_eq!(
+        reader.last(),
+        Some(Err(Error::new(ErrorKind::RecursionLimitExceeded, 2)))
+    );
+}
+
+#[test]
+fn json_org_validator_tests() {
+    for name in ["pass1", "pass2", "pass3"] {
+        let data =
+            std::fs::read(format!("tests/it/json_org_validator_tests/{}.json", name)).unwrap();
+        assert_eq!(to_value(&data), parse(&data), "{}", name);
+    }
+    for i in 1..=33 {
+        let data =
+            std::fs::read(format!("tests/it/json_org_validator_tests/fail{}.json", i)).unwrap();
+        assert_eq!(to_value(&data), parse(&data), "fail{}.json", i);
+    }
+}
+
+proptest! {
+    #[test]
+    fn same_as_parse(data in "[\\[\\]{}\":,0-9a-z\\\\ .-]{0,20}") {
+        prop_assert_eq!(to_value(data.as_bytes()), parse(data.as_bytes()));
+    }
+
+    #[test]
+    fn same_as_parse_relaxed(data in "[\\[\\]{}\"':,0-9a-z/*\\\\ .+-]{0,20}") {
+        let options = ParseOptions::default().with_relaxed(true);
+        prop_assert_eq!(
+            to_value_with_options(data.as_bytes(), &options),
+            parse_with_options(data.as_bytes(), &options)
+        );
+    }
+}
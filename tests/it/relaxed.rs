@@ -0,0 +1,304 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_lazy_with_options, parse_pointer_with_options, parse_projected_with_options,
+    parse_stream_with_options, parse_with_options, to_string, to_string_pretty, validate,
+    validate_with_options, Error, ErrorKind, Number, ParseOptions, Projection, Value,
+};
+
+fn relaxed() -> ParseOptions {
+    ParseOptions::default().with_relaxed(true)
+}
+
+fn parse_relaxed(json: &[u8]) -> Result<Value<'_>, Error> {
+    parse_with_options(json, &relaxed())
+}
+
+#[test]
+fn comments() -> Result<(), Error> {
+    let data: &[u8] = br#"// a config
+    {
+        /* the name */ "a": [1, /* two */ 2], // trailing
+        "b" /* before the colon */ : /**/ null
+    } /* end */ // end"#;
+    assert_eq!(parse_relaxed(data)?, parse(br#"{"a": [1, 2], "b": null}"#)?);
+    assert_eq!(parse_relaxed(b"1//")?, parse(b"1")?);
+
+    assert_eq!(
+        parse_relaxed(b"[1 /* unterminated"),
+        Err(Error::new(ErrorKind::InvalidEOF, 18))
+    );
+    assert_eq!(
+        parse_relaxed(b"[1, / 2]"),
+        Err(Error::new(ErrorKind::InvalidToken(b'/'), 4))
+    );
+    assert_eq!(
+        parse(b"[1, // 2\n]"),
+        Err(Error::new(ErrorKind::InvalidToken(b'/'), 4))
+    );
+    Ok(())
+}
+
+#[test]
+fn trailing_commas() -> Result<(), Error> {
+    assert_eq!(
+        parse_relaxed(br#"{"a": [1, 2, ], "b": {"c": 3,},}"#)?,
+        parse(br#"{"a": [1, 2], "b": {"c": 3}}"#)?
+    );
+    assert_eq!(parse_relaxed(b"[1, // two\n]")?, parse(b"[1]")?);
+
+    assert_eq!(
+        parse_relaxed(b"[,]"),
+        Err(Error::new(ErrorKind::InvalidToken(b','), 1))
+    );
+    assert_eq!(
+        parse_relaxed(b"[1,,]"),
+        Err(Error::new(ErrorKind::InvalidToken(b','), 3))
+    );
+    assert_eq!(
+        parse_relaxed(b"{,}"),
+        Err(Error::new(ErrorKind::InvalidStringToken(b','), 1))
+    );
+    assert_eq!(
+        parse(b"[1,]"),
+        Err(Error::new(ErrorKind::InvalidToken(b']'), 3))
+    );
+    Ok(())
+}
+
+#[test]
+fn single_quoted_strings() -> Result<(), Error> {
+    assert_eq!(
+        parse_relaxed(br#"['a"b\'c\n', '']"#)?,
+        parse(br#"["a\"b'c\n", ""]"#)?
+    );
+    assert_eq!(parse_relaxed(br#"{'a': 1}"#)?, parse(br#"{"a": 1}"#)?);
+
+    assert_eq!(
+        parse_relaxed(br#""\'""#),
+        Err(Error::new(ErrorKind::InvalidEscaped(b'\''), 1))
+    );
+    assert_eq!(
+        parse_relaxed(b"'a"),
+        Err(Error::new(ErrorKind::InvalidEOF, 2))
+    );
+    assert_eq!(
+        parse(b"'a'"),
+        Err(Error::new(ErrorKind::InvalidToken(b'\''), 0))
+    );
+    Ok(())
+}
+
+#[test]
+fn identifier_keys() -> Result<(), Error> {
+    assert_eq!(
+        parse_relaxed(br#"{a_1: 1, $b:2, _: {Infinity: 3}}"#)?,
+        parse(br#"{"a_1": 1, "$b": 2, "_": {"Infinity": 3}}"#)?
+    );
+
+    assert_eq!(
+        parse_relaxed(b"{1a: 1}"),
+        Err(Error::new(ErrorKind::InvalidStringToken(b'1'), 1))
+    );
+    assert_eq!(
+        parse_relaxed(b"{a-b: 1}"),
+        Err(Error::new(ErrorKind::InvalidToken(b'-'), 2))
+    );
+    assert_eq!(
+        parse(b"{a: 1}"),
+        Err(Error::new(ErrorKind::InvalidStringToken(b'a'), 1))
+    );
+    Ok(())
+}
+
+#[test]
+fn numbers() -> Result<(), Error> {
+    let integer =
+        |x: &'static [u8], e: &'static [u8]| Value::Number(Number::Integer(x.into(), e.into()));
+    let float =
+        |x: &'static [u8], e: &'static [u8]| Value::Number(Number::Float(x.into(), e.into()));
+    let cases: &[(&[u8], Value)] = &[
+        (b"+1", integer(b"1", b"")),
+        (b"+1.5e2", float(b"1.5", b"2")),
+        (b".5", float(b"0.5", b"")),
+        (b"-.5e-1", float(b"-0.5", b"-1")),
+        (b"+.5", float(b"0.5", b"")),
+        (b"0x1F", integer(b"31", b"")),
+        (b"-0XfF", integer(b"-255", b"")),
+        (b"+0x0", integer(b"0", b"")),
+        (b"Infinity", float(b"Infinity", b"")),
+        (b"+Infinity", float(b"Infinity", b"")),
+        (b"-Infinity", float(b"-Infinity", b"")),
+        (b"NaN", float(b"NaN", b"")),
+        (b"-1", integer(b"-1", b"")),
+        (b"1.5E+3", float(b"1.5", b"+3")),
+    ];
+    for (data, expected) in cases {
+        assert_eq!(parse_relaxed(data)?, *expected, "{:?}", data);
+    }
+
+    // normalized numbers are written as JSON, and non-finite numbers as `null`
+    let value = parse_relaxed(b"[+1, .5, 0x10]")?;
+    assert_eq!(to_string(&value), "[1,0.5,16]");
+    let value = parse_relaxed(b"[Infinity, -Infinity, +Infinity, NaN, -NaN]")?;
+    assert_eq!(to_string(&value), "[null,null,null,null,null]");
+
+    let errors: &[(&[u8], Error)] = &[
+        (b"0x", Error::new(ErrorKind::InvalidEOF, 2)),
+        (b"[0x]", Error::new(ErrorKind::InvalidHex(b']'), 3)),
+        (b"0xg", Error::new(ErrorKind::InvalidHex(b'g'), 2)),
+        (
+            b"0x100000000000000000000000000000000",
+            Error::new(ErrorKind::NumberOverflow, 0),
+        ),
+        (b"Inf", Error::new(ErrorKind::InvalidToken(b'I'), 0)),
+        (b"-N", Error::new(ErrorKind::InvalidToken(b'N'), 1)),
+        (b"+", Error::new(ErrorKind::InvalidEOF, 1)),
+        (b".", Error::new(ErrorKind::NumberWithEmptyFraction, 1)),
+        (b"01", Error::new(ErrorKind::NumberWithLeadingZero, 1)),
+    ];
+    for (data, expected) in errors {
+        assert_eq!(parse_relaxed(data), Err(expected.clone()), "{:?}", data);
+    }
+    assert_eq!(
+        parse(b"+1"),
+        Err(Error::new(ErrorKind::InvalidToken(b'+'), 0))
+    );
+    Ok(())
+}
+
+#[test]
+fn non_finite_conversions() -> Result<(), Error> {
+    let number = |data| match parse_relaxed(data) {
+        Ok(Value::Number(number)) => number,
+        other => panic!("{:?}", other),
+    };
+    assert_eq!(number(b"Infinity").to_f64(), Ok(f64::INFINITY));
+    assert_eq!(number(b"-Infinity").to_f32(), Ok(f32::NEG_INFINITY));
+    assert!(number(b"NaN").to_f64().unwrap().is_nan());
+    assert_eq!(number(b"Infinity").to_i64(), Err(ErrorKind::NumberOverflow));
+    assert_eq!(number(b"NaN").to_u64(), Err(ErrorKind::NumberPrecisionLoss));
+    assert_eq!(number(b"0x1F").to_u64(), Ok(31));
+    Ok(())
+}
+
+#[test]
+fn entry_points() -> Result<(), Error> {
+    let options = relaxed();
+    let data: &[u8] = br#"{a: [1, 'b',], /* c */ d: {e: +1,},}"#;
+    let expected = parse(br#"{"a": [1, "b"], "d": {"e": 1}}"#)?;
+
+    validate_with_options(data, &options)?;
+    assert_eq!(
+        parse_lazy_with_options(data, &options)?.to_value()?,
+        expected
+    );
+    let lazy = parse_lazy_with_options(data, &options)?;
+    assert_eq!(
+        lazy.get("a")?.unwrap().index(1)?.unwrap().as_bytes(),
+        b"'b'"
+    );
+    assert_eq!(lazy.get("d")?.unwrap().iter_object().unwrap().count(), 1);
+    assert_eq!(
+        parse_pointer_with_options(data, "/d/e", &options)?,
+        Some(parse(b"1")?)
+    );
+    let projection = Projection::new([["a"]]);
+    assert_eq!(
+        parse_projected_with_options(data, &projection, &options)?,
+        parse(br#"{"a": [1, "b"]}"#)?
+    );
+
+    let values = parse_stream_with_options(b"1 // one\n2 /* two */", options)
+        .map(|item| item.map(|(value, _)| value))
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(values, vec![parse(b"1")?, parse(b"2")?]);
+    Ok(())
+}
+
+/// Documents of the relaxed syntax
+fn arb_relaxed() -> impl Strategy<Value = String> {
+    let leaf = prop::sample::select(vec![
+        "Infinity",
+        "-Infinity",
+        "+Infinity",
+        "NaN",
+        "-NaN",
+        "+1",
+        ".5",
+        "-.5e3",
+        "0x1F",
+        "-0XaB",
+        "1E+2",
+        "'a\"b'",
+        "'\\''",
+        "\"c\\n\"",
+        "null",
+        "true",
+    ])
+    .prop_map(String::from);
+    let key = prop::sample::select(vec!["a", "$_1", "'b c'", "\"d\""]);
+    let comment = prop::sample::select(vec!["", " ", "/* [ */", "// ]\n"]);
+    leaf.prop_recursive(4, 32, 4, move |inner| {
+        let items = prop::collection::vec((inner.clone(), comment.clone()), 0..4);
+        let entries = prop::collection::vec((key.clone(), inner, comment.clone()), 0..4);
+        prop_oneof![
+            (items, any::<bool>()).prop_map(|(items, trailing)| {
+                let items = items
+                    .iter()
+                    .map(|(item, comment)| format!("{comment}{item}"))
+                    .collect::<Vec<_>>();
+                let trailing = if trailing && !items.is_empty() {
+                    ","
+                } else {
+                    ""
+                };
+                format!("[{}{trailing}]", items.join(","))
+            }),
+            (entries, any::<bool>()).prop_map(|(entries, trailing)| {
+                let entries = entries
+                    .iter()
+                    .map(|(key, value, comment)| format!("{key}{comment}: {value}"))
+                    .collect::<Vec<_>>();
+                let trailing = if trailing && !entries.is_empty() {
+                    ","
+                } else {
+                    ""
+                };
+                format!("{{{}{trailing}}}", entries.join(", "))
+            }),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn written_as_json(data in arb_relaxed()) {
+        let value = parse_relaxed(data.as_bytes()).unwrap();
+        for json in [to_string(&value), to_string_pretty(&value)] {
+            prop_assert_eq!(validate(json.as_bytes()), Ok(()), "{}", json);
+            prop_assert!(serde_json::from_str::<serde_json::Value>(&json).is_ok(), "{}", json);
+        }
+    }
+
+    #[test]
+    fn superset_of_json(data in "[\\[\\]{}\":,0-9a-z\\\\ .]{0,20}") {
+        if let Ok(value) = parse(data.as_bytes()) {
+            prop_assert_eq!(parse_relaxed(data.as_bytes()), Ok(value));
+        }
+    }
+
+    #[test]
+    fn validate_same_as_parse(data in "[\\[\\]{}\"':,/*\n0-9a-fxINn+ .-]{0,20}") {
+        let options = relaxed();
+        let expected = parse_with_options(data.as_bytes(), &options);
+        prop_assert_eq!(
+            validate_with_options(data.as_bytes(), &options),
+            expected.as_ref().map(|_| ()).map_err(Clone::clone)
+        );
+        prop_assert_eq!(
+            parse_lazy_with_options(data.as_bytes(), &options).and_then(|x| x.to_value()),
+            expected
+        );
+    }
+}
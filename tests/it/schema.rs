@@ -0,0 +1,333 @@
+use json_deserializer::{parse, Error, JsonSchema, SchemaError, ValidationError};
+
+fn compile(schema: &[u8]) -> JsonSchema {
+    JsonSchema::compile(&parse(schema).unwrap()).unwrap()
+}
+
+/// Returns the sorted instance and schema paths of the errors of validating `json`.
+/// Their order depends on the order of keywords, which depends on `preserve_order`.
+fn errors(schema: &JsonSchema, json: &[u8]) -> Vec<(String, String)> {
+    match schema.validate(&parse(json).unwrap()) {
+        Ok(()) => vec![],
+        Err(errors) => {
+            let mut paths = errors
+                .into_iter()
+                .map(|e| (e.instance_path, e.schema_path))
+                .collect::<Vec<_>>();
+            paths.sort();
+            paths
+        }
+    }
+}
+
+fn paths(paths: &[(&str, &str)]) -> Vec<(String, String)> {
+    let mut paths = paths
+        .iter()
+        .map(|(a, b)| (a.to_string(), b.to_string()))
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+type Examples<'a> = &'a [&'a [u8]];
+
+#[test]
+fn types() {
+    // schema, valid values and invalid values
+    let cases: &[(&[u8], Examples, Examples)] = &[
+        (br#"{"type": "null"}"#, &[b"null"], &[b"0", b"false"]),
+        (
+            br#"{"type": "boolean"}"#,
+            &[b"true"],
+            &[b"null", b"\"true\""],
+        ),
+        (br#"{"type": "object"}"#, &[b"{}"], &[b"[]"]),
+        (br#"{"type": "array"}"#, &[b"[]"], &[b"{}"]),
+        (br#"{"type": "string"}"#, &[b"\"\""], &[b"1"]),
+        (br#"{"type": "number"}"#, &[b"1", b"1.5e3"], &[b"\"1\""]),
+        (
+            br#"{"type": "integer"}"#,
+            &[b"1", b"-1.0", b"1.5e3", b"1e400"],
+            &[b"1.5", b"1e-3", b"null"],
+        ),
+        (
+            br#"{"type": ["string", "null"]}"#,
+            &[b"null", b"\"a\""],
+            &[b"1"],
+        ),
+        (b"true", &[b"1", b"{}"], &[]),
+        (b"false", &[], &[b"1", b"{}"]),
+        (b"{}", &[b"1", b"{}"], &[]),
+    ];
+    for (schema, valid, invalid) in cases {
+        let compiled = compile(schema);
+        for json in *valid {
+            assert!(
+                compiled.is_valid(&parse(json).unwrap()),
+                "{} {}",
+                String::from_utf8_lossy(schema),
+                String::from_utf8_lossy(json)
+            );
+        }
+        for json in *invalid {
+            assert!(
+                !compiled.is_valid(&parse(json).unwrap()),
+                "{} {}",
+                String::from_utf8_lossy(schema),
+                String::from_utf8_lossy(json)
+            );
+        }
+    }
+}
+
+#[test]
+fn objects() -> Result<(), Error> {
+    let schema = compile(
+        br#"{
+            "type": "object",
+            "properties": {"a": {"type": "integer"}, "b/c": {"type": "string"}},
+            "required": ["a", "d"],
+            "additionalProperties": {"type": "boolean"}
+        }"#,
+    );
+    assert_eq!(
+        errors(&schema, br#"{"a": 1, "b/c": "x", "d": true}"#),
+        vec![]
+    );
+    assert_eq!(
+        errors(&schema, br#"{"a": "1", "b/c": 2, "e": null}"#),
+        paths(&[
+            ("/e", "/additionalProperties/type"),
+            ("/a", "/properties/a/type"),
+            ("/b~1c", "/properties/b~1c/type"),
+            ("", "/required"),
+        ])
+    );
+
+    let error = schema.validate(&parse(b"{}")?).unwrap_err();
+    assert_eq!(
+        error,
+        vec![ValidationError {
+            instance_path: "".to_string(),
+            schema_path: "/required".to_string(),
+            message: r#"missing required properties "a", "d""#.to_string(),
+        }]
+    );
+    assert_eq!(
+        error[0].to_string(),
+        r#"missing required properties "a", "d" at """#
+    );
+
+    // object keywords do not apply to other types
+    assert!(!schema.is_valid(&parse(b"1")?));
+    assert!(compile(br#"{"required": ["a"]}"#).is_valid(&parse(b"[1]")?));
+    Ok(())
+}
+
+#[test]
+fn arrays() {
+    let schema = compile(
+        br#"{
+            "prefixItems": [{"type": "string"}],
+            "items": {"type": "integer"},
+            "minItems": 1,
+            "maxItems": 3
+        }"#,
+    );
+    assert_eq!(errors(&schema, br#"["a", 1, 2]"#), vec![]);
+    assert_eq!(
+        errors(&schema, br#"[1, "a", 2, 3]"#),
+        paths(&[
+            ("", "/maxItems"),
+            ("/0", "/prefixItems/0/type"),
+            ("/1", "/items/type"),
+        ])
+    );
+    assert_eq!(errors(&schema, b"[]"), paths(&[("", "/minItems")]));
+
+    let schema = compile(br#"{"items": {"items": {"type": "null"}}}"#);
+    assert_eq!(
+        errors(&schema, b"[[null], [null, 1]]"),
+        paths(&[("/1/1", "/items/items/type")])
+    );
+}
+
+#[test]
+fn values() {
+    let schema = compile(br#"{"enum": [1, "a", [null], {"b": 2.0}]}"#);
+    for json in [&b"1.0"[..], b"10e-1", b"\"a\"", b"[null]", br#"{"b": 2}"#] {
+        assert!(schema.is_valid(&parse(json).unwrap()), "{:?}", json);
+    }
+    for json in [&b"2"[..], b"\"b\"", b"[]", br#"{"b": 2, "c": 3}"#, b"true"] {
+        assert!(!schema.is_valid(&parse(json).unwrap()), "{:?}", json);
+    }
+
+    let schema = compile(br#"{"const": 18446744073709551616}"#);
+    assert!(schema.is_valid(&parse(b"18446744073709551616").unwrap()));
+    assert!(!schema.is_valid(&parse(b"18446744073709551617").unwrap()));
+}
+
+#[test]
+fn numbers_and_strings() {
+    let schema = compile(br#"{"minimum": 1, "exclusiveMaximum": 2.5}"#);
+    assert_eq!(errors(&schema, b"1"), vec![]);
+    assert_eq!(errors(&schema, b"2.4"), vec![]);
+    assert_eq!(errors(&schema, b"0.5"), paths(&[("", "/minimum")]));
+    assert_eq!(errors(&schema, b"2.5"), paths(&[("", "/exclusiveMaximum")]));
+    assert_eq!(errors(&schema, b"\"0\""), vec![]);
+
+    let schema = compile(br#"{"maximum": 1, "exclusiveMinimum": -1}"#);
+    assert_eq!(errors(&schema, b"-1"), paths(&[("", "/exclusiveMinimum")]));
+    assert_eq!(errors(&schema, b"1e400"), paths(&[("", "/maximum")]));
+
+    // lengths are in code points
+    let schema = compile(br#"{"minLength": 2, "maxLength": 3}"#);
+    assert_eq!(errors(&schema, "\"éé\"".as_bytes()), vec![]);
+    assert_eq!(
+        errors(&schema, "\"\\u00e9\"".as_bytes()),
+        paths(&[("", "/minLength")])
+    );
+    assert_eq!(errors(&schema, b"\"abcd\""), paths(&[("", "/maxLength")]));
+}
+
+#[test]
+fn combinations() {
+    let schema = compile(
+        br#"{
+            "allOf": [{"type": "integer"}, {"minimum": 0}],
+            "anyOf": [{"maximum": 10}, {"minimum": 100}],
+            "oneOf": [{"minimum": 5}, {"maximum": 200}],
+            "not": {"const": 7}
+        }"#,
+    );
+    assert_eq!(errors(&schema, b"1"), vec![]);
+    assert_eq!(errors(&schema, b"300"), vec![]);
+    assert_eq!(
+        errors(&schema, b"-1.5"),
+        paths(&[("", "/allOf/0/type"), ("", "/allOf/1/minimum")])
+    );
+    assert_eq!(
+        errors(&schema, b"50"),
+        paths(&[("", "/anyOf"), ("", "/oneOf")])
+    );
+    assert_eq!(
+        errors(&schema, b"7"),
+        paths(&[("", "/oneOf"), ("", "/not")])
+    );
+}
+
+#[test]
+fn references() {
+    let schema = compile(
+        br##"{
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "value": {"$ref": "#/$defs/value"},
+                        "children": {"type": "array", "items": {"$ref": "#/$defs/node"}}
+                    },
+                    "required": ["value"]
+                },
+                "value": {"type": "integer"},
+                "a~b": {"type": "null"},
+                "d e": {"type": "string"}
+            },
+            "properties": {
+                "root": {"$ref": "#/$defs/node"},
+                "c": {"$ref": "#/$defs/a~0b"},
+                "d": {"$ref": "#/$defs/d%20e"}
+            }
+        }"##,
+    );
+    let json = br#"{"root": {"value": 1, "children": [{"value": 2, "children": []}, {"value": "3"}, {}]}}"#;
+    assert_eq!(
+        errors(&schema, json),
+        paths(&[
+            ("/root/children/1/value", "/$defs/value/type"),
+            ("/root/children/2", "/$defs/node/required"),
+        ])
+    );
+    assert_eq!(
+        errors(&schema, br#"{"c": 1}"#),
+        paths(&[("/c", "/$defs/a~0b/type")])
+    );
+    assert_eq!(
+        errors(&schema, br#"{"d": 1}"#),
+        paths(&[("/d", "/$defs/d e/type")])
+    );
+
+    // recursion through nested values is allowed
+    let schema = compile(
+        br##"{"anyOf": [{"type": "integer"}, {"type": "array", "items": {"$ref": "#"}}]}"##,
+    );
+    assert!(schema.is_valid(&parse(b"[1, [2, [3]]]").unwrap()));
+    assert!(!schema.is_valid(&parse(b"[1, [2, [\"3\"]]]").unwrap()));
+}
+
+#[test]
+fn compile_errors() {
+    let error = |path: &str, message: &str| SchemaError {
+        path: path.to_string(),
+        message: message.to_string(),
+    };
+    let cases: &[(&[u8], SchemaError)] = &[
+        (b"1", error("", "schemas must be objects or booleans")),
+        (
+            br#"{"type": "int"}"#,
+            error("/type", "unknown type \"int\""),
+        ),
+        (
+            br#"{"properties": {"a": []}}"#,
+            error("/properties/a", "schemas must be objects or booleans"),
+        ),
+        (
+            br#"{"minLength": -1}"#,
+            error("/minLength", "expected a non-negative integer"),
+        ),
+        (
+            br#"{"anyOf": []}"#,
+            error("/anyOf", "expected a non-empty array of schemas"),
+        ),
+        (
+            br#"{"required": "a"}"#,
+            error("/required", "expected an array of strings"),
+        ),
+        (
+            br#"{"$ref": "other.json"}"#,
+            error(
+                "/$ref",
+                "only JSON Pointers within the document are supported",
+            ),
+        ),
+        (
+            br##"{"$ref": "#/$defs/a%2"}"##,
+            error("/$ref", "invalid percent-encoding"),
+        ),
+        (
+            br##"{"$ref": "#/$defs/%FF"}"##,
+            error("/$ref", "invalid percent-encoding"),
+        ),
+        (
+            br##"{"$ref": "#/$defs/a"}"##,
+            error("/$ref", "\"#/$defs/a\" does not exist"),
+        ),
+        (
+            br##"{"$ref": "#"}"##,
+            error("", "the schema is applied to itself"),
+        ),
+        (
+            br##"{"$defs": {"a": {"allOf": [{"$ref": "#/$defs/b"}]}, "b": {"not": {"$ref": "#/$defs/a"}}}, "$ref": "#/$defs/a"}"##,
+            error("/$defs/a", "the schema is applied to itself"),
+        ),
+    ];
+    for (schema, expected) in cases {
+        assert_eq!(
+            JsonSchema::compile(&parse(schema).unwrap()).unwrap_err(),
+            *expected,
+            "{}",
+            String::from_utf8_lossy(schema)
+        );
+    }
+    assert_eq!(cases[1].1.to_string(), "unknown type \"int\" at \"/type\"");
+}
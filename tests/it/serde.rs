@@ -0,0 +1,264 @@
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Deserialize;
+
+use json_deserializer::{from_slice, from_slice_with_options, Error, ErrorKind, ParseOptions};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Record<'a> {
+    name: &'a str,
+    #[serde(borrow)]
+    comment: Cow<'a, str>,
+    id: u32,
+    score: f64,
+    delta: i64,
+    valid: bool,
+    parent: Option<u32>,
+    tags: Vec<String>,
+}
+
+#[test]
+fn record() -> Result<(), Error> {
+    let data: &[u8] = br#"{
+        "name": "a",
+        "comment": "b\nc",
+        "id": 1,
+        "score": 1.5e2,
+        "delta": -3,
+        "valid": true,
+        "parent": null,
+        "tags": ["x", "y"],
+        "unknown": {"a": [1, {}]}
+    }"#;
+
+    let record: Record = from_slice(data)?;
+    assert_eq!(
+        record,
+        Record {
+            name: "a",
+            comment: Cow::Owned("b\nc".to_string()),
+            id: 1,
+            score: 150.0,
+            delta: -3,
+            valid: true,
+            parent: None,
+            tags: vec!["x".to_string(), "y".to_string()],
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn borrowed() -> Result<(), Error> {
+    let data: &[u8] = br#"["a", "b"]"#;
+
+    let items: Vec<&str> = from_slice(data)?;
+    assert_eq!(items, vec!["a", "b"]);
+    // zero-copy: the strings point to the input
+    assert_eq!(items[0].as_ptr(), data[2..].as_ptr());
+
+    // escaped strings cannot be borrowed
+    assert!(from_slice::<Vec<&str>>(br#"["\n"]"#).is_err());
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Point(i32, i32),
+    Rectangle { width: u8, height: u8 },
+}
+
+#[test]
+fn enums() -> Result<(), Error> {
+    let data: &[u8] = br#"["Empty", {"Circle": 1.0}, {"Point": [1, -2]}, {"Rectangle": {"width": 1, "height": 2}}]"#;
+
+    let shapes: Vec<Shape> = from_slice(data)?;
+    assert_eq!(
+        shapes,
+        vec![
+            Shape::Empty,
+            Shape::Circle(1.0),
+            Shape::Point(1, -2),
+            Shape::Rectangle {
+                width: 1,
+                height: 2
+            }
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn numbers() -> Result<(), Error> {
+    assert_eq!(from_slice::<u64>(b"18446744073709551615")?, u64::MAX);
+    assert_eq!(from_slice::<i64>(b"-9223372036854775808")?, i64::MIN);
+    assert_eq!(
+        from_slice::<f64>(b"18446744073709551616")?,
+        18446744073709551616.0
+    );
+    assert_eq!(from_slice::<f64>(b"1")?, 1.0);
+    assert_eq!(from_slice::<f64>(b"-0.1e-2")?, -0.001);
+    assert_eq!(
+        from_slice::<f64>(b"1e400").unwrap_err(),
+        Error::new(ErrorKind::NumberOverflow, 5)
+    );
+
+    for value in [i128::MIN, i128::MAX] {
+        assert_eq!(from_slice::<i128>(value.to_string().as_bytes())?, value);
+    }
+    for value in [u128::MIN, u128::MAX] {
+        assert_eq!(from_slice::<u128>(value.to_string().as_bytes())?, value);
+    }
+    assert!(from_slice::<u128>(b"340282366920938463463374607431768211456").is_err());
+    assert!(from_slice::<u128>(b"-1").is_err());
+    let map: HashMap<u128, i128> = from_slice(
+        br#"{"340282366920938463463374607431768211455": -170141183460469231731687303715884105728}"#,
+    )?;
+    assert_eq!(map[&u128::MAX], i128::MIN);
+
+    assert_eq!(from_slice::<f32>(b"3.4028235e38")?, f32::MAX);
+    assert_eq!(from_slice::<f32>(b"-1e-50")?, -0.0);
+    assert_eq!(
+        from_slice::<f32>(b"1e39").unwrap_err(),
+        Error::new(ErrorKind::NumberOverflow, 4)
+    );
+    assert_eq!(
+        from_slice::<Vec<f32>>(b"[1.5, -1e39]").unwrap_err(),
+        Error::new(ErrorKind::NumberOverflow, 11)
+    );
+    Ok(())
+}
+
+#[test]
+fn map() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": [1, 2], "b": []}"#;
+
+    let map: HashMap<String, Vec<u8>> = from_slice(data)?;
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], vec![1, 2]);
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize)]
+struct Id(u16);
+
+#[test]
+fn map_keys() -> Result<(), Error> {
+    let map: HashMap<u32, &str> = from_slice(br#"{"1": "a", "20": "b"}"#)?;
+    assert_eq!(map, HashMap::from([(1, "a"), (20, "b")]));
+
+    // escaped keys are parsed after unescaping
+    let map: BTreeMap<i64, bool> = from_slice(br#"{"-1": true, "\u0032": false}"#)?;
+    assert_eq!(map, BTreeMap::from([(-1, true), (2, false)]));
+
+    let map: BTreeMap<bool, Id> = from_slice(br#"{"true": 1, "false": 0}"#)?;
+    assert_eq!(map, BTreeMap::from([(true, Id(1)), (false, Id(0))]));
+
+    let map: BTreeMap<Id, f32> = from_slice(br#"{"2": 1.5}"#)?;
+    assert_eq!(map, BTreeMap::from([(Id(2), 1.5)]));
+
+    // keys that are not numbers error with the expected type, located after the key
+    for data in [&br#"{"a": 1}"#[..], br#"{"1 ": 1}"#, br#"{"01": 1}"#] {
+        let error = from_slice::<HashMap<u32, u8>>(data).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Custom(_)), "{:?}", error);
+        assert_eq!(error.offset(), data.len() - 4);
+    }
+    let error = from_slice::<HashMap<bool, u8>>(br#"{"1": 1}"#).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Custom(_)));
+    let error = from_slice::<HashMap<u8, u8>>(br#"{"256": 1}"#).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Custom(_)));
+    Ok(())
+}
+
+#[test]
+fn relaxed() -> Result<(), Error> {
+    let options = ParseOptions::default().with_relaxed(true);
+    let data: &[u8] = br#"{
+        // a comment
+        a: [1, +2, .5, 0x10,],
+        'b': 'c',
+        shape: {Circle: Infinity,}, /* another comment */
+    }"#;
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Relaxed<'a> {
+        a: Vec<f64>,
+        b: &'a str,
+        shape: Shape,
+    }
+    let value: Relaxed = from_slice_with_options(data, &options)?;
+    assert_eq!(
+        value,
+        Relaxed {
+            a: vec![1.0, 2.0, 0.5, 16.0],
+            b: "c",
+            shape: Shape::Circle(f64::INFINITY),
+        }
+    );
+    let shapes: Vec<Shape> = from_slice_with_options(b"['Empty', {'Point': [1, -2,]},]", &options)?;
+    assert_eq!(shapes, vec![Shape::Empty, Shape::Point(1, -2)]);
+
+    // strict JSON by default
+    assert_eq!(
+        from_slice::<Vec<u32>>(b"[1,]"),
+        Err(Error::new(ErrorKind::InvalidToken(b']'), 3))
+    );
+    assert_eq!(
+        from_slice::<Vec<u32>>(b"[1] // a"),
+        Err(Error::new(ErrorKind::InvalidEOF, 4))
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    // invalid JSON
+    assert_eq!(
+        from_slice::<Vec<u8>>(b"[1 2]").unwrap_err(),
+        Error::new(ErrorKind::MissingComma(b'2'), 3)
+    );
+    assert_eq!(
+        from_slice::<Vec<u8>>(b"[1] 2").unwrap_err(),
+        Error::new(ErrorKind::InvalidEOF, 4)
+    );
+
+    // valid JSON of a different type: the error is located after the value
+    let error = from_slice::<Vec<u8>>(b"[1, 256]").unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::Custom(_)));
+    assert_eq!(error.offset(), 7);
+
+    let error = from_slice::<Record>(br#"{"name": "a"}"#).unwrap_err();
+    assert_eq!(
+        error,
+        Error::new(ErrorKind::Custom("missing field `comment`".to_string()), 12)
+    );
+
+    let options = ParseOptions::default().with_max_depth(1);
+    assert_eq!(
+        from_slice_with_options::<Vec<Vec<u8>>>(b"[[1]]", &options).unwrap_err(),
+        Error::new(ErrorKind::RecursionLimitExceeded, 1)
+    );
+}
+
+#[test]
+fn same_as_serde_json() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Item {
+        a: Option<Vec<(u8, String)>>,
+        b: HashMap<String, bool>,
+        c: (),
+    }
+
+    let data = r#"[
+        {"a": [[1, "€"], [2, "\u20AC"]], "b": {"x": true}, "c": null},
+        {"a": null, "b": {}, "c": null}
+    ]"#
+    .as_bytes();
+
+    let expected: Vec<Item> = serde_json::from_slice(data).unwrap();
+    let result: Vec<Item> = from_slice(data).unwrap();
+    assert_eq!(result, expected);
+}
@@ -0,0 +1,66 @@
+use json_deserializer::{
+    parse_stream, parse_stream_with_options, Error, ErrorKind, Number, Object, ParseOptions, Value,
+};
+
+#[test]
+fn concatenated() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a":1}{"a":2}[]"#;
+
+    let items = parse_stream(data).collect::<Result<Vec<_>, _>>()?;
+    let spans = items.iter().map(|x| x.1.clone()).collect::<Vec<_>>();
+    assert_eq!(spans, vec![0..7, 7..14, 14..16]);
+    assert_eq!(items[2].0, Value::Array(vec![]));
+    assert_eq!(&data[items[1].1.clone()], br#"{"a":2}"#);
+    Ok(())
+}
+
+#[test]
+fn whitespace_separated() -> Result<(), Error> {
+    let data: &[u8] = b" 1 \"a\"\n\ttrue null\n";
+
+    let items = parse_stream(data).collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        items,
+        vec![
+            (
+                Value::Number(Number::Integer(b"1".into(), b"".into())),
+                1..2
+            ),
+            (Value::String("a".into()), 3..6),
+            (Value::Bool(true), 8..12),
+            (Value::Null, 13..17),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn empty() {
+    assert_eq!(parse_stream(b"").count(), 0);
+    assert_eq!(parse_stream(b" \n ").count(), 0);
+}
+
+#[test]
+fn error() {
+    let data: &[u8] = b"{}{\"a\" 1}{}";
+
+    let mut iter = parse_stream(data);
+    assert_eq!(iter.next(), Some(Ok((Value::Object(Object::new()), 0..2))));
+    assert_eq!(
+        iter.next(),
+        Some(Err(Error::new(ErrorKind::InvalidToken(b'1'), 7)))
+    );
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn options() {
+    let options = ParseOptions::default().with_max_depth(1);
+    let mut iter = parse_stream_with_options(b"[1] [[1]]", options);
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(iter.byte_offset(), 3);
+    assert_eq!(
+        iter.next(),
+        Some(Err(Error::new(ErrorKind::RecursionLimitExceeded, 5)))
+    );
+}
@@ -0,0 +1,111 @@
+use std::borrow::Cow;
+
+use proptest::prelude::*;
+
+use json_deserializer::{parse, Error, ErrorKind, Value};
+
+#[test]
+fn non_ascii_with_escapes() -> Result<(), Error> {
+    let data = r#"["é\n", "\tü€😀\"", "éé😀"]"#;
+
+    let item = parse(data.as_bytes())?;
+    assert_eq!(
+        item,
+        Value::Array(vec![
+            Value::String(Cow::Owned("é\n".to_string())),
+            Value::String(Cow::Owned("\tü€😀\"".to_string())),
+            Value::String(Cow::Owned("éé😀".to_string())),
+        ])
+    );
+    Ok(())
+}
+
+#[test]
+fn delete_is_not_a_control_character() {
+    assert_eq!(parse(b"\"\x7f\""), Ok(Value::String(Cow::Borrowed("\x7f"))));
+    assert!(parse(b"\"\x1f\"").is_err());
+}
+
+#[test]
+fn invalid_utf8_with_escapes() {
+    // \xc3\x28 is invalid utf8
+    let data: &[u8] = &[b'"', b'\\', b'n', b'a', 195, 40, b'"'];
+    assert_eq!(parse(data), Err(Error::new(ErrorKind::InvalidUtf8, 4)));
+
+    let data: &[u8] = &[b'"', 195, 40, b'\\', b'n', b'"'];
+    assert_eq!(parse(data), Err(Error::new(ErrorKind::InvalidUtf8, 1)));
+}
+
+#[test]
+fn errors_across_blocks() {
+    // strings are scanned many bytes at a time, so errors are checked at every position
+    for length in 0..70 {
+        let contents = "a".repeat(length);
+        let data = format!("\"{}\x01\"", contents);
+        assert_eq!(
+            parse(data.as_bytes()),
+            Err(Error::new(
+                ErrorKind::StringWithControlCharacters,
+                1 + length
+            )),
+        );
+
+        let data = format!("\"{}", contents);
+        assert_eq!(
+            parse(data.as_bytes()),
+            Err(Error::new(ErrorKind::InvalidEOF, 1 + length))
+        );
+
+        let data = format!("\"{}\\\"{}\"", contents, contents);
+        let expected = format!("{}\"{}", contents, contents);
+        assert_eq!(parse(data.as_bytes()), Ok(Value::String(expected.into())));
+    }
+}
+
+/// Writes `value` as a JSON string, escaping each char with the respective strategy
+fn escape(value: &str, modes: &[u8]) -> String {
+    let mut result = "\"".to_string();
+    for (c, mode) in value.chars().zip(modes.iter().cycle()) {
+        match (c, mode % 3) {
+            ('"', _) => result.push_str("\\\""),
+            ('\\', _) => result.push_str("\\\\"),
+            (c, 0) if !c.is_control() => result.push(c),
+            ('\n', 1) => result.push_str("\\n"),
+            (c, _) => {
+                let mut buffer = [0; 2];
+                for unit in c.encode_utf16(&mut buffer) {
+                    result.push_str(&format!("\\u{:04X}", unit));
+                }
+            }
+        }
+    }
+    result.push('"');
+    result
+}
+
+proptest! {
+    #[test]
+    fn same_as_serde_json(value in any::<String>()) {
+        let data = serde_json::to_string(&value).unwrap();
+        prop_assert_eq!(parse(data.as_bytes()).unwrap(), Value::String(value.into()));
+    }
+
+    #[test]
+    fn escaped_same_as_serde_json(
+        value in any::<String>(),
+        modes in prop::collection::vec(any::<u8>(), 1..10)
+    ) {
+        let data = escape(&value, &modes);
+        let expected: String = serde_json::from_str(&data).unwrap();
+        prop_assert_eq!(&expected, &value);
+        prop_assert_eq!(parse(data.as_bytes()).unwrap(), Value::String(expected.into()));
+    }
+
+    #[test]
+    fn validity_same_as_serde_json(data in "\"([a\"\\\\nu0é\x01\x7f]|\\\\ud800|\\\\udc00){0,80}\"?") {
+        match serde_json::from_str::<String>(&data) {
+            Ok(expected) => prop_assert_eq!(parse(data.as_bytes()), Ok(Value::String(expected.into()))),
+            Err(_) => prop_assert!(parse(data.as_bytes()).is_err()),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use std::borrow::Cow;
+
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, parse_tape, parse_tape_with_options, parse_with_options, DuplicateKeys, Error,
+    ErrorKind, Number, ParseOptions, TapeEntry, Value,
+};
+
+fn integer(value: &[u8]) -> Value<'_> {
+    Value::Number(Number::Integer(value.into(), b"".into()))
+}
+
+#[test]
+fn entries() -> Result<(), Error> {
+    let tape = parse_tape(br#"{"a": [1, {}], "b": "c"}"#)?;
+    // positions are in words, and owned keys and strings, numbers and starts take two
+    assert_eq!(
+        tape.entries().collect::<Vec<_>>(),
+        vec![
+            TapeEntry::StartObject { len: 2, end: 13 },
+            TapeEntry::Key("a"),
+            TapeEntry::StartArray { len: 2, end: 10 },
+            TapeEntry::Number(Number::Integer(b"1".into(), b"".into())),
+            TapeEntry::StartObject { len: 0, end: 9 },
+            TapeEntry::EndObject { start: 7 },
+            TapeEntry::EndArray { start: 3 },
+            TapeEntry::Key("b"),
+            TapeEntry::String("c"),
+            TapeEntry::EndObject { start: 0 },
+        ]
+    );
+
+    let tape = parse_tape(b" null ")?;
+    assert_eq!(tape.entries().collect::<Vec<_>>(), vec![TapeEntry::Null]);
+    Ok(())
+}
+
+#[test]
+fn get() -> Result<(), Error> {
+    let tape = parse_tape(br#"{"a": [1, {"b": "c"}], "d": null, "e\n": true}"#)?;
+    let root = tape.root();
+
+    let a = root.get("a").unwrap();
+    assert_eq!(a.entries().count(), 7);
+    assert!(a.get("a").is_none());
+
+    let b = a.index(1).unwrap().get("b").unwrap();
+    assert_eq!(b.entry(), TapeEntry::String("c"));
+    assert_eq!(b.to_value(), Value::String("c".into()));
+    assert!(a.index(2).is_none());
+    assert!(b.index(0).is_none());
+
+    assert_eq!(root.get("d").unwrap().to_value(), Value::Null);
+    assert_eq!(root.get("e\n").unwrap().to_value(), Value::Bool(true));
+    assert!(root.get("f").is_none());
+    Ok(())
+}
+
+#[test]
+fn iter() -> Result<(), Error> {
+    let tape = parse_tape(br#"[{"a": [[1]], "b": 2}, [], "c"]"#)?;
+    let root = tape.root();
+    assert!(root.iter_object().is_none());
+
+    let items = root.iter_array().unwrap().collect::<Vec<_>>();
+    assert_eq!(items.len(), 3);
+    assert_eq!(items[1].to_value(), Value::Array(vec![]));
+    assert_eq!(items[2].to_value(), Value::String("c".into()));
+
+    let entries = items[0]
+        .iter_object()
+        .unwrap()
+        .map(|(k, v)| (k, v.to_value()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            ("a", Value::Array(vec![Value::Array(vec![integer(b"1")])])),
+            ("b", integer(b"2")),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn owned_text() -> Result<(), Error> {
+    // escaped strings and normalized numbers are not in the input
+    let options = ParseOptions::default().with_relaxed(true);
+    let data: &[u8] = br#"{"a\n": ["b\u00e9", .5e-3, 0x1F, 2E5, -Infinity]}"#;
+    let tape = parse_tape_with_options(data, &options)?;
+    let a = tape.root().get("a\n").unwrap();
+    assert_eq!(a.index(0).unwrap().entry(), TapeEntry::String("b\u{e9}"));
+    assert_eq!(
+        a.index(1).unwrap().entry(),
+        TapeEntry::Number(Number::Float(b"0.5".into(), b"-3".into()))
+    );
+    assert_eq!(
+        a.index(2).unwrap().entry(),
+        TapeEntry::Number(Number::Integer(b"31".into(), b"".into()))
+    );
+    assert_eq!(
+        a.index(3).unwrap().entry(),
+        TapeEntry::Number(Number::Integer(b"2".into(), b"5".into()))
+    );
+    assert_eq!(tape.root().to_value(), parse_with_options(data, &options)?);
+
+    // strings are borrowed even when the rest of the input is not UTF-8, e.g. a comment
+    let data: &[u8] = b"[\"a\", 1 /* \xff */]";
+    let tape = parse_tape_with_options(data, &options)?;
+    assert_eq!(tape.root().to_value(), parse_with_options(data, &options)?);
+    assert!(matches!(
+        tape.root().index(0).unwrap().to_value(),
+        Value::String(Cow::Borrowed("a"))
+    ));
+    Ok(())
+}
+
+#[test]
+fn duplicate_keys() -> Result<(), Error> {
+    let data: &[u8] = br#"{"a": 1, "b": [], "a": 2}"#;
+    for policy in [
+        DuplicateKeys::LastWins,
+        DuplicateKeys::FirstWins,
+        DuplicateKeys::KeepAll,
+    ] {
+        let options = ParseOptions::default().with_duplicate_keys(policy);
+        let tape = parse_tape_with_options(data, &options)?;
+        let expected = if policy == DuplicateKeys::FirstWins {
+            b"1"
+        } else {
+            b"2"
+        };
+        assert_eq!(tape.root().get("a").unwrap().to_value(), integer(expected));
+        assert_eq!(tape.root().iter_object().unwrap().count(), 3);
+        assert_eq!(
+            tape.root().to_value(),
+            parse_with_options(data, &options)?,
+            "{:?}",
+            policy
+        );
+    }
+
+    let options = ParseOptions::default().with_duplicate_keys(DuplicateKeys::Error);
+    assert_eq!(
+        parse_tape_with_options(data, &options),
+        Err(Error::new(ErrorKind::DuplicateKey("a".to_string()), 18))
+    );
+    Ok(())
+}
+
+#[test]
+fn errors() {
+    let cases: &[(&[u8], ErrorKind, usize)] = &[
+        (b"", ErrorKind::InvalidEOF, 0),
+        (b"[1, 2 3]", ErrorKind::MissingComma(b'3'), 6),
+        (b"[1, 2] 3", ErrorKind::InvalidEOF, 7),
+        (br#"{"a" 1}"#, ErrorKind::InvalidToken(b'1'), 5),
+        (br#"{"a": 1, 2}"#, ErrorKind::InvalidStringToken(b'2'), 9),
+        (b"[true, trua]", ErrorKind::InvalidTrueToken(*b"trua"), 7),
+    ];
+    for (data, kind, offset) in cases {
+        assert_eq!(
+            parse_tape(data),
+            Err(Error::new(kind.clone(), *offset)),
+            "{:?}",
+            data
+        );
+    }
+
+    let options = ParseOptions::default().with_max_depth(2);
+    assert_eq!(
+        parse_tape_with_options(br#"{"a": [{}]}"#, &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 7))
+    );
+}
+
+proptest! {
+    #[test]
+    fn same_as_parse(data in "[\\[\\]{}\":,0-9a-z\\\\ .-]{0,20}") {
+        prop_assert_eq!(
+            parse_tape(data.as_bytes()).map(|tape| tape.root().to_value()),
+            parse(data.as_bytes())
+        );
+    }
+
+    #[test]
+    fn same_as_parse_relaxed(data in "[\\[\\]{}\"':,0-9a-z/*\\\\ .+-]{0,20}") {
+        let options = ParseOptions::default().with_relaxed(true).with_duplicate_keys(DuplicateKeys::Error);
+        prop_assert_eq!(
+            parse_tape_with_options(data.as_bytes(), &options).map(|tape| tape.root().to_value()),
+            parse_with_options(data.as_bytes(), &options)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, skip_value, skip_value_with_options, validate, validate_with_options, Error, ErrorKind,
+    ParseOptions,
+};
+
+/// Counts the allocations of each thread, so that tests running in parallel do not interfere
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|x| x.set(x.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(|x| x.get());
+    let result = f();
+    (result, ALLOCATIONS.with(|x| x.get()) - before)
+}
+
+#[test]
+fn does_not_allocate() {
+    let data = r#"{"a\nb": [1, 2.5e-3, "é😀", {"c": [true, false, null]}]}"#.as_bytes();
+    assert!(allocations(|| parse(data)).1 > 0);
+    assert_eq!(allocations(|| validate(data)), (Ok(()), 0));
+
+    let data: &[u8] = br#"{"a": [1, "\u12"]}"#;
+    let (result, count) = allocations(|| validate(data));
+    assert_eq!(count, 0);
+    assert_eq!(result, Err(parse(data).unwrap_err()));
+}
+
+#[test]
+fn skip() -> Result<(), Error> {
+    let data: &[u8] = br#" {"a": ["b\n", 1.5]} [] 1,null "#;
+
+    let mut values = data;
+    let (result, count) = allocations(|| skip_value(&mut values));
+    result?;
+    assert_eq!(count, 0);
+    assert_eq!(values, br#" [] 1,null "#);
+    skip_value(&mut values)?;
+    skip_value(&mut values)?;
+    assert_eq!(values, b",null ");
+    assert_eq!(
+        skip_value(&mut values),
+        Err(Error::new(ErrorKind::InvalidToken(b','), 0))
+    );
+    assert_eq!(values, b",null ");
+    values = &values[1..];
+    skip_value(&mut values)?;
+    assert_eq!(values, b" ");
+    assert_eq!(
+        skip_value(&mut values),
+        Err(Error::new(ErrorKind::InvalidEOF, 1))
+    );
+    Ok(())
+}
+
+#[test]
+fn skip_errors() {
+    let mut values: &[u8] = b"[1, [2 3]] 4";
+    assert_eq!(
+        skip_value(&mut values),
+        Err(Error::new(ErrorKind::MissingComma(b'3'), 7))
+    );
+    assert_eq!(values, b"[1, [2 3]] 4");
+
+    let options = ParseOptions::default().with_max_depth(1);
+    let mut values: &[u8] = b"[[]]";
+    assert_eq!(
+        skip_value_with_options(&mut values, &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 1))
+    );
+}
+
+#[test]
+fn json_org_validator_tests() {
+    let pass: [&[u8]; 3] = [
+        include_bytes!("json_org_validator_tests/pass1.json"),
+        include_bytes!("json_org_validator_tests/pass2.json"),
+        include_bytes!("json_org_validator_tests/pass3.json"),
+    ];
+    for data in pass {
+        assert_eq!(validate(data), Ok(()));
+    }
+
+    for i in 1..=33 {
+        let path = format!("tests/it/json_org_validator_tests/fail{}.json", i);
+        let data = std::fs::read(path).unwrap();
+        assert_eq!(validate(&data), parse(&data).map(|_| ()), "fail{}.json", i);
+    }
+}
+
+#[test]
+fn max_depth() {
+    let options = ParseOptions::default().with_max_depth(2);
+    assert_eq!(validate_with_options(b"[{\"a\": 1}]", &options), Ok(()));
+    assert_eq!(
+        validate_with_options(b"[{\"a\": []}]", &options),
+        Err(Error::new(ErrorKind::RecursionLimitExceeded, 7))
+    );
+}
+
+proptest! {
+    #[test]
+    fn same_errors_as_parse(data in "[\\[\\]{}\":,0-9a-z\\\\ .-]{0,20}") {
+        prop_assert_eq!(validate(data.as_bytes()), parse(data.as_bytes()).map(|_| ()));
+    }
+
+    #[test]
+    fn skip_same_errors_as_parse(data in "[\\[\\]{}\":,0-9a-z\\\\ .-]{0,20}") {
+        let mut values = data.as_bytes();
+        match skip_value(&mut values) {
+            Ok(()) => {
+                let length = data.len() - values.len();
+                prop_assert!(parse(&data.as_bytes()[..length]).is_ok());
+            }
+            Err(e) => prop_assert_eq!(Err(e), parse(data.as_bytes()).map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn same_errors_as_parse_strings(data in "\"(\\PC|\\\\[\"\\\\/bfnrtu]|\\\\u[0-9a-fA-FdD]{0,4}){0,10}\"?") {
+        prop_assert_eq!(validate(data.as_bytes()), parse(data.as_bytes()).map(|_| ()));
+    }
+}
@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use json_deserializer::{parse, visit, Error, ErrorKind, Number, Visitor};
+
+/// Sums the numbers of the `"a"` keys and counts the other tokens
+#[derive(Default)]
+struct Stats {
+    depth: usize,
+    max_depth: usize,
+    key: Option<String>,
+    sum: i64,
+    strings: usize,
+    others: usize,
+}
+
+impl<'a> Visitor<'a> for Stats {
+    fn visit_null(&mut self) -> Result<(), ErrorKind> {
+        self.others += 1;
+        Ok(())
+    }
+
+    fn visit_bool(&mut self, _: bool) -> Result<(), ErrorKind> {
+        self.others += 1;
+        Ok(())
+    }
+
+    fn visit_number(&mut self, value: Number<'a>) -> Result<(), ErrorKind> {
+        if self.key.as_deref() == Some("a") {
+            self.sum += value.to_i64()?;
+        }
+        Ok(())
+    }
+
+    fn visit_str(&mut self, _: Cow<'a, str>) -> Result<(), ErrorKind> {
+        self.strings += 1;
+        Ok(())
+    }
+
+    fn begin_object(&mut self) -> Result<(), ErrorKind> {
+        self.depth += 1;
+        self.max_depth = self.max_depth.max(self.depth);
+        Ok(())
+    }
+
+    fn key(&mut self, key: Cow<'a, str>) -> Result<(), ErrorKind> {
+        self.key = Some(key.into_owned());
+        Ok(())
+    }
+
+    fn end_object(&mut self) -> Result<(), ErrorKind> {
+        self.depth -= 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn stats() -> Result<(), Error> {
+    let data: &[u8] = br#"[{"a": 1, "b": "c"}, {"a": 2, "d": {"a": 3}}, null, true, "e"]"#;
+
+    let mut stats = Stats::default();
+    visit(data, &mut stats)?;
+    assert_eq!(stats.sum, 6);
+    assert_eq!(stats.strings, 2);
+    assert_eq!(stats.others, 2);
+    assert_eq!(stats.max_depth, 2);
+    assert_eq!(stats.depth, 0);
+    Ok(())
+}
+
+#[test]
+fn default_methods() -> Result<(), Error> {
+    struct Nothing;
+    impl Visitor<'_> for Nothing {}
+
+    visit(br#"{"a": [1, "b", null, true]}"#, &mut Nothing)
+}
+
+#[test]
+fn visitor_error() {
+    let data: &[u8] = br#"[{"a": 1}, {"a": 1.5}]"#;
+
+    let mut stats = Stats::default();
+    assert_eq!(
+        visit(data, &mut stats),
+        Err(Error::new(ErrorKind::NumberPrecisionLoss, 20))
+    );
+    assert_eq!(stats.sum, 1);
+}
+
+#[test]
+fn invalid_json() {
+    let data: &[u8] = br#"[{"a": 1}, {"a" 1}]"#;
+
+    let mut stats = Stats::default();
+    assert_eq!(visit(data, &mut stats), Err(parse(data).unwrap_err()));
+}
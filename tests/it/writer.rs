@@ -0,0 +1,101 @@
+use proptest::prelude::*;
+
+use json_deserializer::{
+    parse, to_string, to_string_pretty, to_vec, write, Error, Number, Object, Value,
+};
+
+#[test]
+fn compact() -> Result<(), Error> {
+    let data = r#"{ "a" : [1, -2.5e+10, 1E2, true, false, null, {}, []], "b": "c" }"#;
+
+    let value = parse(data.as_bytes())?;
+    assert_eq!(
+        to_string(&value),
+        r#"{"a":[1,-2.5e+10,1e2,true,false,null,{},[]],"b":"c"}"#
+    );
+    assert_eq!(to_vec(&value), to_string(&value).into_bytes());
+    assert_eq!(format!("{}", value), to_string(&value));
+    Ok(())
+}
+
+#[test]
+fn pretty() -> Result<(), Error> {
+    let data = br#"{"a":[1,{"b":null}],"c":{},"d":[]}"#;
+
+    let value = parse(data)?;
+    let expected = r#"{
+  "a": [
+    1,
+    {
+      "b": null
+    }
+  ],
+  "c": {},
+  "d": []
+}"#;
+    assert_eq!(to_string_pretty(&value), expected);
+    assert_eq!(format!("{:#}", value), expected);
+    Ok(())
+}
+
+#[test]
+fn escapes() {
+    let value = Value::String("a\"\\/\n\r\t\x08\x0c\x00\x1f\x7fé€😀".into());
+    let result = to_string(&value);
+    assert_eq!(
+        result,
+        "\"a\\\"\\\\/\\n\\r\\t\\b\\f\\u0000\\u001f\x7fé€😀\""
+    );
+    assert_eq!(parse(result.as_bytes()).unwrap(), value);
+}
+
+#[test]
+fn numbers_are_verbatim() {
+    let data = "[0.10000000000000000000000000001, 123456789012345678901234567890, -0.0]";
+
+    let value = parse(data.as_bytes()).unwrap();
+    assert_eq!(
+        to_string(&value),
+        "[0.10000000000000000000000000001,123456789012345678901234567890,-0.0]"
+    );
+}
+
+#[test]
+fn writer_errors() {
+    let mut object = Object::new();
+    object.insert(
+        "a".into(),
+        Value::Number(Number::Integer(vec![255].into(), b"".into())),
+    );
+    let mut result = String::new();
+    assert!(write(&mut result, &Value::Object(object)).is_err());
+}
+
+fn arb_json() -> impl Strategy<Value = serde_json::Value> {
+    use serde_json::Value;
+    let leaf = prop_oneof![
+        Just(Value::Null),
+        any::<bool>().prop_map(Value::Bool),
+        any::<f64>().prop_map(Value::from),
+        any::<i64>().prop_map(Value::from),
+        "[a-z \"\\\\/\n\t]*".prop_map(Value::String),
+    ];
+    leaf.prop_recursive(4, 20, 10, |inner| {
+        prop_oneof![
+            prop::collection::vec(inner.clone(), 0..10).prop_map(Value::Array),
+            prop::collection::btree_map("[a-z\"]*", inner, 0..10)
+                .prop_map(|x| Value::Object(x.into_iter().collect())),
+        ]
+    })
+}
+
+proptest! {
+    #[test]
+    fn same_as_serde_json(value in arb_json()) {
+        let data = serde_json::to_string(&value).unwrap();
+        prop_assert_eq!(to_string(&parse(data.as_bytes()).unwrap()), data);
+
+        let data = serde_json::to_string_pretty(&value).unwrap();
+        prop_assert_eq!(to_string_pretty(&parse(data.as_bytes()).unwrap()), data);
+    }
+}
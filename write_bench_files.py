@@ -36,3 +36,8 @@ for log2_size in range(10,22, 2):
     data = [{"a": False}] * size
     with open(f"data/object_bool_{log2_size}.json", "w") as f:
         json.dump(data, f)
+
+    # pretty-printed, where most whitespace is indentation
+    data = [{"a": "this is something", "b": [1, 2.5, True, None], "c": {"d": False}}] * size
+    with open(f"data/object_indented_{log2_size}.json", "w") as f:
+        json.dump(data, f, indent=4)